@@ -0,0 +1,96 @@
+use crate::field_rule::{compare_attribute, discard_attribute};
+use syn::punctuated::Punctuated;
+use syn::{parse_quote, parse_str, AttrStyle, Attribute, Meta, Token};
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct AttributePolicy {
+    kept: Option<Vec<String>>,
+    dropped: Vec<String>,
+    added: Vec<Attribute>,
+}
+
+impl AttributePolicy {
+    pub(crate) fn keep(&mut self, attributes: Vec<String>) {
+        self.kept.get_or_insert_with(Vec::new).extend(attributes);
+    }
+
+    pub(crate) fn drop(&mut self, attributes: Vec<String>) {
+        self.dropped.extend(attributes);
+    }
+
+    pub(crate) fn add(&mut self, attribute: String) {
+        let meta = match parse_str::<Meta>(attribute.as_str()) {
+            Ok(value) => value,
+            Err(error) => panic!("Invalid attribute '{}': {}", attribute, error)
+        };
+        self.added.push(Attribute {
+            pound_token: Default::default(),
+            style: AttrStyle::Outer,
+            bracket_token: Default::default(),
+            meta,
+        });
+    }
+
+    pub(crate) fn apply(&self, attributes: &[Attribute]) -> Vec<Attribute> {
+        let mut attributes = attributes.iter()
+            .filter(|attribute| {
+                match &self.kept {
+                    Some(kept) => kept.iter().any(|value| Self::matches(attribute, value)),
+                    None => true
+                }
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        for value in self.dropped.iter() {
+            attributes = match parse_str::<syn::Path>(value) {
+                Ok(path) => attributes.into_iter()
+                    .filter(|attribute| attribute.path() != &path)
+                    .collect(),
+                Err(_) => discard_attribute(&attributes, value)
+            };
+        }
+        for attribute in self.added.iter() {
+            if attribute.path().is_ident("derive") {
+                Self::merge_derive(&mut attributes, attribute);
+            }
+            else if !attributes.contains(attribute) {
+                attributes.push(attribute.clone());
+            }
+        }
+        attributes
+    }
+
+    pub(self) fn merge_derive(attributes: &mut Vec<Attribute>, attribute: &Attribute) {
+        fn derive_paths(attribute: &Attribute) -> Vec<syn::Path> {
+            attribute.parse_args_with(Punctuated::<syn::Path, Token![,]>::parse_terminated)
+                .map(|paths| paths.into_iter().collect())
+                .unwrap_or_default()
+        }
+        let mut existing = attributes.iter()
+            .filter(|value| value.path().is_ident("derive"))
+            .flat_map(derive_paths)
+            .collect::<Vec<_>>();
+        let mut added = Vec::new();
+        for path in derive_paths(attribute).into_iter() {
+            if !existing.contains(&path) {
+                existing.push(path.clone());
+                added.push(path);
+            }
+        }
+        if added.is_empty() {
+            return;
+        }
+        match attributes.iter_mut().find(|value| value.path().is_ident("derive")) {
+            Some(value) => {
+                let mut paths = derive_paths(value);
+                paths.append(&mut added);
+                *value = parse_quote!(#[derive(#(#paths),*)]);
+            }
+            None => attributes.push(parse_quote!(#[derive(#(#added),*)]))
+        }
+    }
+
+    pub(self) fn matches(attribute: &Attribute, value: &str) -> bool {
+        compare_attribute(attribute, value)
+    }
+}
\ No newline at end of file
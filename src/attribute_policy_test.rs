@@ -0,0 +1,80 @@
+use crate::generator::Generator;
+use crate::test_utils::fixtures::{generator, module_with_nested_item_attributes, struct_with_item_attributes};
+use ast_shaper::items::item::ItemTrait;
+use ast_shaper::items::module_item::ModuleItem;
+use quote::__private::TokenStream;
+use quote::quote;
+use rstest::rstest;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[rstest]
+#[case(|generator: &mut Generator| {
+    generator.drop_item_attributes(["derive"]);
+}, quote!())]
+#[case(|generator: &mut Generator| {
+    generator.keep_item_attributes(["doc"]);
+}, quote!())]
+#[case(|generator: &mut Generator| {
+    generator.drop_item_attributes(["derive(Serialize)"]);
+}, quote!(#[derive(Debug)]))]
+#[case(|generator: &mut Generator| {
+    generator.drop_item_attributes(["derive(Serialize, Debug)"])
+        .add_item_attribute("derive(Debug, Clone, Default)");
+}, quote!(#[derive(Debug, Clone, Default)]))]
+#[case(|generator: &mut Generator| {
+    generator.add_item_attribute("derive(Debug)");
+}, quote!(#[derive(Debug, Serialize)]))]
+#[case(|generator: &mut Generator| {
+    generator.add_item_attribute("derive(Clone, Debug)");
+}, quote!(#[derive(Debug, Serialize, Clone)]))]
+#[case(|generator: &mut Generator| {
+    generator.drop_item_attributes(["derive"])
+        .add_item_attribute("derive(Clone, Clone)");
+}, quote!(#[derive(Clone)]))]
+fn item_attribute_policy(
+    mut generator: Generator,
+    struct_with_item_attributes: syn::Item,
+    #[case] policy: fn(&mut Generator),
+    #[case] expected_attributes: TokenStream
+) {
+    policy(&mut generator);
+    let items = generator.generate(&struct_with_item_attributes);
+    let attributes = &items.first().unwrap().item.attrs;
+    assert_eq!(
+        expected_attributes.to_string(),
+        quote!(#(#attributes)*).to_string()
+    );
+}
+
+#[rstest]
+#[case(|_: &mut Generator| {}, vec![
+    quote!(#[derive(Debug, Serialize)] #[serde(rename_all = "camelCase")]),
+    quote!(#[derive(Clone)])
+])]
+#[case(|generator: &mut Generator| {
+    generator.drop_item_attributes(["serde"])
+        .add_item_attribute("derive(Debug)");
+}, vec![
+    quote!(#[derive(Debug, Serialize)]),
+    quote!(#[derive(Clone, Debug)])
+])]
+fn nested_item_attribute_policy(
+    module_with_nested_item_attributes: ModuleItem,
+    #[case] policy: fn(&mut Generator),
+    #[case] expected_attributes: Vec<TokenStream>
+) {
+    let item = module_with_nested_item_attributes.items.first().unwrap().to_syn_item();
+    let mut generator = Generator::new(Rc::new(RefCell::new(vec![module_with_nested_item_attributes])));
+    policy(&mut generator);
+    let attributes = generator.generate(&item).iter()
+        .map(|builder| {
+            let attributes = &builder.item.attrs;
+            quote!(#(#attributes)*).to_string()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        expected_attributes.iter().map(|value| value.to_string()).collect::<Vec<_>>(),
+        attributes
+    );
+}
\ No newline at end of file
@@ -14,4 +14,95 @@ pub(crate) const RESERVED_TYPES: &[&str] = &[
     "String",
     "Vec",
     "HashMap"
+];
+
+pub(crate) const KEYWORDS: &[&str] = &[
+    "abstract",
+    "as",
+    "async",
+    "await",
+    "become",
+    "box",
+    "break",
+    "const",
+    "continue",
+    "crate",
+    "do",
+    "dyn",
+    "else",
+    "enum",
+    "extern",
+    "false",
+    "final",
+    "fn",
+    "for",
+    "gen",
+    "if",
+    "impl",
+    "in",
+    "let",
+    "loop",
+    "macro",
+    "match",
+    "mod",
+    "move",
+    "mut",
+    "override",
+    "priv",
+    "pub",
+    "ref",
+    "return",
+    "self",
+    "Self",
+    "static",
+    "struct",
+    "super",
+    "trait",
+    "true",
+    "try",
+    "type",
+    "typeof",
+    "unsafe",
+    "unsized",
+    "use",
+    "virtual",
+    "where",
+    "while",
+    "yield"
+];
+
+pub(crate) const NON_RAW_KEYWORDS: &[&str] = &[
+    "crate",
+    "self",
+    "Self",
+    "super"
+];
+
+pub(crate) const REGEX_SYNTAX: &[char] = &[
+    '^',
+    '$',
+    '(',
+    ')',
+    '+',
+    '|',
+    '\\',
+    '{',
+    '}'
+];
+
+pub(crate) const COLLECTION_TYPES: &[&str] = &[
+    "BTreeMap",
+    "BTreeSet",
+    "HashMap",
+    "HashSet"
+];
+
+pub(crate) const WRAPPER_TYPES: &[&str] = &[
+    "Arc",
+    "Box",
+    "Cell",
+    "Mutex",
+    "Rc",
+    "RefCell",
+    "RwLock"
 ];
\ No newline at end of file
@@ -0,0 +1,17 @@
+use syn::{parse_quote, Attribute};
+
+pub(crate) fn doc_attributes(lines: Vec<String>) -> Vec<Attribute> {
+    lines.iter()
+        .map(|line| {
+            let line = match line.is_empty() {
+                true => String::new(),
+                false => format!(" {}", line)
+            };
+            parse_quote!(#[doc = #line])
+        })
+        .collect()
+}
+
+pub(crate) fn is_doc_attribute(attribute: &Attribute) -> bool {
+    attribute.path().is_ident("doc")
+}
\ No newline at end of file
@@ -1,20 +1,31 @@
-use crate::field_type_segment::{FieldTypeSegment, InnerFieldTypeSegment};
+use crate::doc::{doc_attributes, is_doc_attribute};
+use crate::field_type_segment::{FieldConverter, FieldTypeSegment, InnerFieldTypeSegment};
 use crate::generator::Generator;
+use crate::ident::{create_escaped_ident, escape, unraw, validate_ident};
+use crate::naming::Naming;
 use ast_shaper::utils::path::Path;
 use ast_shaper::utils::statement::{Expr, ExprMethodChainCall, Statement};
 use ast_shaper::utils::{create_generic_type, create_ident};
+use quote::{quote, ToTokens};
 use std::collections::HashMap;
 use syn::punctuated::Punctuated;
-use syn::spanned::Spanned;
-use syn::{Attribute, Block, FnArg, Ident, ImplItem, ImplItemFn, Pat, PatIdent, PatType, Receiver, ReturnType, Signature, Stmt, Type, TypePath, TypeReference, Visibility};
+use syn::visit_mut::{self, VisitMut};
+use syn::{parse_quote, Attribute, Block, FieldValue, FnArg, Ident, ImplItem, ImplItemFn, LitStr, Member, Meta, Pat, PatIdent, PatType, Receiver, ReturnType, Signature, Stmt, Token, Type, TypePath, TypeReference, Visibility};
 
 #[derive(Debug, Clone)]
 pub(crate) struct Field {
     generator: Generator,
     pub item: syn::Field,
     pub ident: String,
+    pub target_ident: String,
     pub ty: FieldTypeSegment,
     pub is_required: bool,
+    pub is_optional_type: bool,
+    pub is_skipped: bool,
+    pub is_into: bool,
+    pub visibility: Visibility,
+    pub default: Option<syn::Expr>,
+    pub(crate) default_source: Option<String>,
 }
 
 impl Field {
@@ -37,9 +48,72 @@ impl Field {
         Self {
             generator,
             item: field,
-            ident: field_ident,
+            ident: field_ident.clone(),
+            target_ident: field_ident,
             ty: field_type,
             is_required,
+            is_optional_type: !is_required,
+            is_skipped: false,
+            is_into: false,
+            visibility: Visibility::Public(Default::default()),
+            default: None,
+            default_source: None,
+        }
+    }
+
+    pub(crate) fn apply_builder_attributes(&mut self) {
+        let attributes = self.item.attrs.iter()
+            .filter(|attribute| attribute.path().is_ident("builder"))
+            .cloned()
+            .collect::<Vec<_>>();
+        self.item.attrs.retain(|attribute| !attribute.path().is_ident("builder"));
+        let mut rename = None;
+        let mut default = None;
+        let mut is_skipped = false;
+        for attribute in attributes.iter() {
+            let result = attribute.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    let value: LitStr = meta.value()?.parse()?;
+                    if let Err(error) = validate_ident(&value.value()) {
+                        return Err(meta.error(error));
+                    }
+                    rename = Some(value.value());
+                }
+                else if meta.path.is_ident("default") {
+                    let value: LitStr = meta.value()?.parse()?;
+                    default = Some((value.parse::<syn::Expr>()?, value.value().trim().to_string()));
+                }
+                else if meta.path.is_ident("skip") {
+                    is_skipped = true;
+                }
+                else if meta.path.is_ident("into") {
+                    self.is_into = true;
+                }
+                else {
+                    return Err(meta.error("unsupported builder attribute"));
+                }
+                Ok(())
+            });
+            if let Err(error) = result {
+                panic!("Invalid builder attribute on field '{}': {}", self.target_ident, error);
+            }
+        }
+        if let Some(ident) = rename {
+            self.rename(ident);
+        }
+        match (is_skipped, default) {
+            (true, Some((default, source))) => {
+                self.skip(default);
+                self.default_source = Some(source);
+            }
+            (true, None) => {
+                self.skip(parse_quote!(Default::default()));
+            }
+            (false, Some((default, source))) => {
+                self.set_default(default);
+                self.default_source = Some(source);
+            }
+            (false, None) => {}
         }
     }
 
@@ -48,27 +122,232 @@ impl Field {
     }
 
     pub fn rename(&mut self, ident: String) {
-        self.ident = ident.into();
+        self.ident = escape(&ident);
     }
 
     pub fn map(&mut self, ty: Path) {
         self.ty = FieldTypeSegment::map(&self.generator.clone(), self.ty.clone(), ty);
     }
 
-    pub(self) fn sanitized_ident(&self) -> String {
-        match self.ident.as_str() {
-            "r#type" => "type".to_string(),
-            _ => self.ident.to_string()
+    pub fn map_with(&mut self, ty: Path, converter: FieldConverter) {
+        self.ty = FieldTypeSegment::map_with(&self.generator.clone(), self.ty.clone(), ty, Some(converter));
+    }
+
+    pub fn skip(&mut self, default: syn::Expr) {
+        self.is_skipped = true;
+        self.default = Some(default);
+        self.default_source = None;
+    }
+
+    pub fn set_default(&mut self, default: syn::Expr) {
+        self.is_required = false;
+        self.default = Some(default);
+        self.default_source = None;
+    }
+
+    pub fn set_visibility(&mut self, visibility: Visibility) {
+        self.visibility = visibility;
+    }
+
+    pub fn set_required(&mut self, is_required: bool) {
+        self.is_required = is_required;
+    }
+
+    pub(crate) fn has_fallible_conversion(&self) -> bool {
+        if let InnerFieldTypeSegment::Remap(value) = &self.ty.inner {
+            if value.converter.as_ref().is_some_and(|converter| converter.is_fallible) {
+                return true;
+            }
+        }
+        match &self.ty.target().inner {
+            InnerFieldTypeSegment::Complex(value) => Self::has_fallible_fields(&value.inner),
+            _ => false
+        }
+    }
+
+    pub(crate) fn has_fallible_fields(fields: &[Field]) -> bool {
+        fields.iter().any(|field| !field.is_skipped && field.has_fallible_conversion())
+    }
+
+    pub(crate) fn unsupported_remap(&self) -> Option<(String, String)> {
+        fn type_name(segment: &FieldTypeSegment) -> String {
+            match &segment.inner {
+                InnerFieldTypeSegment::Option(value) => format!("Option<{}>", type_name(&value.underlying_ty)),
+                value => value.to_string()
+            }
+        }
+        if self.is_skipped {
+            return None;
+        }
+        match &self.ty.inner {
+            InnerFieldTypeSegment::Remap(value) if value.converter.is_none() => {
+                match Self::is_supported_remap(&value.source.inner, &value.target.inner) {
+                    true => None,
+                    false => Some((type_name(&value.source), type_name(&value.target)))
+                }
+            }
+            _ => None
+        }
+    }
+
+    pub(crate) fn is_supported_remap(source: &InnerFieldTypeSegment, target: &InnerFieldTypeSegment) -> bool {
+        matches!(
+            (source, target),
+            (InnerFieldTypeSegment::Vec(_), InnerFieldTypeSegment::Vec(_)) |
+            (InnerFieldTypeSegment::Option(_), InnerFieldTypeSegment::Vec(_)) |
+            (InnerFieldTypeSegment::Vec(_), InnerFieldTypeSegment::Set(_)) |
+            (InnerFieldTypeSegment::Set(_), InnerFieldTypeSegment::Vec(_)) |
+            (InnerFieldTypeSegment::Set(_), InnerFieldTypeSegment::Set(_)) |
+            (InnerFieldTypeSegment::Option(_), InnerFieldTypeSegment::Set(_)) |
+            (InnerFieldTypeSegment::Map(_), InnerFieldTypeSegment::Map(_)) |
+            (
+                InnerFieldTypeSegment::Reserved(_) |
+                InnerFieldTypeSegment::Complex(_) |
+                InnerFieldTypeSegment::Option(_) |
+                InnerFieldTypeSegment::Generic(_),
+                InnerFieldTypeSegment::Complex(_) | InnerFieldTypeSegment::Reserved(_)
+            ) |
+            (InnerFieldTypeSegment::Reserved(_), InnerFieldTypeSegment::Vec(_)) |
+            (InnerFieldTypeSegment::Option(_), InnerFieldTypeSegment::Option(_))
+        )
+    }
+
+    pub(crate) fn cfg_attributes(&self) -> Vec<Attribute> {
+        self.item.attrs.iter()
+            .filter(|attribute| attribute.path().is_ident("cfg"))
+            .cloned()
+            .collect()
+    }
+
+    pub(crate) fn is_deprecated(&self) -> bool {
+        !self.deprecated_attributes().is_empty()
+    }
+
+    pub(self) fn deprecated_attributes(&self) -> Vec<Attribute> {
+        self.item.attrs.iter()
+            .filter_map(|attribute| {
+                if attribute.path().is_ident("deprecated") {
+                    return Some(attribute.clone());
+                }
+                let (predicate, entries) = Self::cfg_attr_entries(attribute)?;
+                let entries = entries.into_iter()
+                    .filter(|entry| entry.path().is_ident("deprecated"))
+                    .collect::<Vec<_>>();
+                match entries.is_empty() {
+                    true => None,
+                    false => Some(parse_quote!(#[cfg_attr(#predicate, #(#entries),*)]))
+                }
+            })
+            .collect()
+    }
+
+    pub(self) fn cfg_attr_entries(attribute: &Attribute) -> Option<(Meta, Vec<Meta>)> {
+        if !attribute.path().is_ident("cfg_attr") {
+            return None;
+        }
+        let entries = attribute.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated).ok()?;
+        let mut entries = entries.into_iter();
+        let predicate = entries.next()?;
+        Some((predicate, entries.collect()))
+    }
+
+    pub(crate) fn gate_struct_literal(
+        statement: &mut Stmt,
+        fields: &Vec<Field>,
+        ident: impl Fn(&Field) -> String
+    ) {
+        struct CfgVisitor {
+            attributes: HashMap<String, Vec<Attribute>>
+        }
+        impl VisitMut for CfgVisitor {
+            fn visit_field_value_mut(&mut self, node: &mut FieldValue) {
+                if let Member::Named(value) = &node.member {
+                    if let Some(attributes) = self.attributes.get(&value.to_string()) {
+                        node.attrs.extend(attributes.clone());
+                    }
+                }
+                visit_mut::visit_field_value_mut(self, node);
+            }
+        }
+        let attributes = fields.iter()
+            .map(|field| (ident(field), field.cfg_attributes()))
+            .filter(|(_, attributes)| !attributes.is_empty())
+            .collect::<HashMap<_, _>>();
+        if attributes.is_empty() {
+            return;
+        }
+        CfgVisitor { attributes }.visit_stmt_mut(statement);
+    }
+
+    pub(self) fn generate_doc_attributes(&self) -> Vec<Attribute> {
+        let mut attributes = self.item.attrs.iter()
+            .filter(|attribute| is_doc_attribute(attribute))
+            .cloned()
+            .collect::<Vec<_>>();
+        let naming = self.generator.naming();
+        let mut lines = match attributes.is_empty() {
+            true => vec![],
+            false => vec![String::new()]
+        };
+        match (self.is_required, &self.default, self.is_optional_type) {
+            (true, _, _) => {
+                lines.push(format!(
+                    "This field is required: `{}` panics when it is not set.",
+                    naming.build_method
+                ));
+            }
+            (false, Some(default), _) => {
+                lines.push(format!(
+                    "This field is optional and defaults to `{}`.",
+                    self.default_source.clone().unwrap_or_else(|| default.to_token_stream().to_string())
+                ));
+            }
+            (false, None, true) => {
+                lines.push("This field is optional and defaults to `None`.".to_string());
+            }
+            (false, None, false) => {
+                lines.push("This field is optional and defaults to `Default::default()`.".to_string());
+            }
         }
+        attributes.append(&mut doc_attributes(lines));
+        attributes
+    }
+
+    pub(self) fn stores_explicit_none(&self) -> bool {
+        self.ty.is_option() && (self.ty.is_nested_option() || self.is_required)
+    }
+
+    pub(self) fn sanitized_ident(&self) -> String {
+        unraw(&self.ident)
     }
 
     pub(crate) fn unwrap(&self) -> syn::Field {
+        let field_type = match self.stores_explicit_none() {
+            true => create_generic_type("Option", vec![self.ty.unwrap()]),
+            false => self.ty.unwrap()
+        };
         let field_type = create_generic_type(
             "Option",
-            vec![self.ty.unwrap()]
+            vec![field_type]
         );
         let mut field = self.item.clone();
-        field.ident = Some(create_ident(&self.ident));
+        field.attrs = field.attrs.iter()
+            .filter(|attribute| !attribute.path().is_ident("deprecated"))
+            .filter_map(|attribute| {
+                let (predicate, entries) = match Self::cfg_attr_entries(attribute) {
+                    Some(value) => value,
+                    None => return Some(attribute.clone())
+                };
+                let entries = entries.into_iter()
+                    .filter(|entry| !entry.path().is_ident("deprecated"))
+                    .collect::<Vec<_>>();
+                match entries.is_empty() {
+                    true => None,
+                    false => Some(parse_quote!(#[cfg_attr(#predicate, #(#entries),*)]))
+                }
+            })
+            .collect();
+        field.ident = Some(create_escaped_ident(&self.ident));
         field.ty = Type::Path(TypePath {
             qself: None,
             path: field_type.to_syn_path(),
@@ -76,10 +355,25 @@ impl Field {
         field
     }
 
-    pub(crate) fn generate_new_method(fields: &Vec<Field>) -> ImplItem {
+    pub(crate) fn generate_new_method(naming: &Naming, visibility: Visibility, fields: &Vec<Field>) -> ImplItem {
+        let mut statement = Statement::implicit_return(
+            Expr::Stmt(Statement::struct_literal(
+                Path::new("Self"),
+                fields.iter()
+                    .filter(|field| !field.is_skipped)
+                    .map(|field| {
+                        (
+                            field.ident.clone(),
+                            Expr::Path(Path::new("None"))
+                        )
+                    })
+                    .collect::<HashMap<String, Expr>>()
+            ))
+        );
+        Self::gate_struct_literal(&mut statement, fields, |field| field.ident.clone());
         let item = ImplItem::Fn(ImplItemFn {
             attrs: vec![],
-            vis: Visibility::Public(Default::default()),
+            vis: visibility,
             defaultness: None,
             sig: Signature {
                 constness: None,
@@ -87,7 +381,7 @@ impl Field {
                 unsafety: None,
                 abi: None,
                 fn_token: Default::default(),
-                ident: create_ident("new"),
+                ident: create_ident(&naming.new_method),
                 generics: Default::default(),
                 paren_token: Default::default(),
                 inputs: Punctuated::default(),
@@ -99,32 +393,199 @@ impl Field {
             },
             block: Block {
                 brace_token: Default::default(),
-                stmts: vec![
-                    Statement::implicit_return(
-                        Expr::Stmt(Statement::struct_literal(
-                            Path::new("Self"),
-                            fields.iter()
-                                .map(|field| {
-                                    (
-                                        field.ident.clone(),
-                                        Expr::Path(Path::new("None"))
-                                    )
-                                })
-                                .collect::<HashMap<String, Expr>>()
-                        ))
-                    )
-                ],
+                stmts: vec![statement],
             },
         });
         item
     }
 
+    pub(crate) fn generate_set_methods(&self) -> Vec<ImplItem> {
+        let mut methods = vec![self.generate_set_method()];
+        if let Some(segment) = self.collection_segment() {
+            methods.push(self.generate_set_all_method(segment));
+            methods.push(self.generate_replace_method(segment));
+        }
+        if self.ty.is_option() {
+            methods.push(self.generate_set_option_method());
+            methods.push(self.generate_unset_method());
+        }
+        methods
+    }
+
     pub(crate) fn generate_set_method(&self) -> ImplItem {
-        let ident = self.sanitized_ident();
-        let ident = format!("with_{}", ident);
-        let ident = Ident::new(ident.as_str(), ident.span());
-        let mut arguments = Punctuated::new();
-        arguments.push(FnArg::Receiver(Receiver {
+        let naming = self.generator.naming();
+        let ident = match self.ty.target().is_complex() {
+            true => naming.accessor_ident(&self.sanitized_ident()),
+            false => naming.setter_ident(&self.sanitized_ident())
+        };
+        let arguments = self.generate_set_method_arguments().iter()
+            .map(|(ident, ty)| {
+                let ty = ty.to_syn_path();
+                (
+                    ident.clone(),
+                    match self.is_into {
+                        true => parse_quote!(impl Into<#ty>),
+                        false => Type::Path(TypePath {
+                            qself: None,
+                            path: ty,
+                        })
+                    }
+                )
+            })
+            .collect();
+        let mut statements = match self.is_into {
+            true => {
+                self.generate_set_method_arguments().iter()
+                    .map(|(ident, _)| parse_quote!(let #ident = #ident.into();))
+                    .collect()
+            }
+            false => vec![]
+        };
+        statements.append(&mut self.generate_set_method_statements());
+        let return_type = match &self.ty.target().is_complex() {
+            true => self.ty.target().unwrap(),
+            false => Path::new("Self")
+        };
+        self.generate_method(
+            create_escaped_ident(&ident),
+            arguments,
+            return_type,
+            statements
+        )
+    }
+
+    pub(self) fn generate_set_all_method(&self, segment: &FieldTypeSegment) -> ImplItem {
+        let ident = format!("{}_all", self.generator.naming().setter_ident(&self.sanitized_ident()));
+        let item_type = match &segment.inner {
+            InnerFieldTypeSegment::Vec(value) => value.item.unwrap().to_syn_path().to_token_stream(),
+            InnerFieldTypeSegment::Set(value) => value.item.unwrap().to_syn_path().to_token_stream(),
+            InnerFieldTypeSegment::Map(value) => {
+                let key = value.key.unwrap().to_syn_path();
+                let value = value.value.unwrap().to_syn_path();
+                quote!((#key, #value))
+            }
+            _ => panic!("Expected collection type")
+        };
+        self.generate_method(
+            create_escaped_ident(&ident),
+            vec![(
+                create_ident("values"),
+                parse_quote!(impl IntoIterator<Item = #item_type>)
+            )],
+            Path::new("Self"),
+            vec![
+                Self::generate_init_collection_statement(&self.ident, segment),
+                Statement::method_chain_call(vec![
+                    ExprMethodChainCall::Start {
+                        receiver: Expr::Stmt(Statement::access_field(
+                            Path::new("self"),
+                            Path::new(self.ident.clone())
+                        )),
+                        method: Path::new("as_mut"),
+                        arguments: vec![],
+                    },
+                    ExprMethodChainCall::Chained {
+                        method: Path::new("unwrap"),
+                        arguments: vec![],
+                    },
+                    ExprMethodChainCall::Chained {
+                        method: Path::new("extend"),
+                        arguments: vec![
+                            Expr::Path(Path::new("values"))
+                        ],
+                    }
+                ]),
+                Statement::implicit_return(Expr::Path(Path::new("self")))
+            ]
+        )
+    }
+
+    pub(self) fn generate_replace_method(&self, segment: &FieldTypeSegment) -> ImplItem {
+        let ident = self.generator.naming().replace_ident(&self.sanitized_ident());
+        self.generate_method(
+            create_escaped_ident(&ident),
+            vec![(
+                create_ident("values"),
+                Type::Path(TypePath {
+                    qself: None,
+                    path: segment.unwrap().to_syn_path(),
+                })
+            )],
+            Path::new("Self"),
+            vec![
+                Statement::assign_field(
+                    Path::new("self"),
+                    Path::new(self.ident.clone()),
+                    Expr::Stmt(Statement::call(
+                        Path::new("Some"),
+                        vec![
+                            Expr::Path(Path::new("values"))
+                        ]
+                    ))
+                ),
+                Statement::implicit_return(Expr::Path(Path::new("self")))
+            ]
+        )
+    }
+
+    pub(self) fn generate_set_option_method(&self) -> ImplItem {
+        let ident = format!("{}_opt", self.generator.naming().setter_ident(&self.sanitized_ident()));
+        let value = match self.stores_explicit_none() {
+            true => Expr::Stmt(Statement::call(
+                Path::new("Some"),
+                vec![
+                    Expr::Path(Path::new("value"))
+                ]
+            )),
+            false => Expr::Path(Path::new("value"))
+        };
+        self.generate_method(
+            create_escaped_ident(&ident),
+            vec![(
+                create_ident("value"),
+                Type::Path(TypePath {
+                    qself: None,
+                    path: create_generic_type("Option", vec![self.ty.unwrap()]).to_syn_path(),
+                })
+            )],
+            Path::new("Self"),
+            vec![
+                Statement::assign_field(
+                    Path::new("self"),
+                    Path::new(self.ident.clone()),
+                    value
+                ),
+                Statement::implicit_return(Expr::Path(Path::new("self")))
+            ]
+        )
+    }
+
+    pub(self) fn generate_unset_method(&self) -> ImplItem {
+        let ident = self.generator.naming().unset_ident(&self.sanitized_ident());
+        self.generate_method(
+            create_escaped_ident(&ident),
+            vec![],
+            Path::new("Self"),
+            vec![
+                Statement::assign_field(
+                    Path::new("self"),
+                    Path::new(self.ident.clone()),
+                    Expr::Path(Path::new("None"))
+                ),
+                Statement::implicit_return(Expr::Path(Path::new("self")))
+            ]
+        )
+    }
+
+    pub(self) fn generate_method(
+        &self,
+        ident: Ident,
+        arguments: Vec<(Ident, Type)>,
+        return_type: Path,
+        statements: Vec<Stmt>
+    ) -> ImplItem {
+        let mut inputs = Punctuated::new();
+        inputs.push(FnArg::Receiver(Receiver {
             attrs: vec![],
             reference: Some((Default::default(), None)),
             mutability: Some(Default::default()),
@@ -140,7 +601,7 @@ impl Field {
                 })),
             }))
         }));
-        self.generate_set_method_arguments().iter()
+        arguments.into_iter()
             .map(|(ident, ty)| {
                 FnArg::Typed(PatType {
                     attrs: vec![],
@@ -148,46 +609,22 @@ impl Field {
                         attrs: vec![],
                         by_ref: None,
                         mutability: None,
-                        ident: ident.clone(),
+                        ident,
                         subpat: None,
                     })),
                     colon_token: Default::default(),
-                    ty: Box::new(Type::Path(TypePath {
-                        qself: None,
-                        path: ty.to_syn_path(),
-                    })),
+                    ty: Box::new(ty),
                 })
             })
             .for_each(|argument| {
-                arguments.push(argument);
+                inputs.push(argument);
             });
-        let return_type = match &self.ty.is_complex() {
-            true => {
-                ReturnType::Type(Default::default(), Box::new(Type::Reference(TypeReference {
-                    and_token: Default::default(),
-                    lifetime: None,
-                    mutability: Some(Default::default()),
-                    elem: Box::new(Type::Path(TypePath {
-                        qself: None,
-                        path: self.ty.unwrap().to_syn_path(),
-                    })),
-                })))
-            }
-            false => {
-                ReturnType::Type(Default::default(), Box::new(Type::Reference(TypeReference {
-                    and_token: Default::default(),
-                    lifetime: None,
-                    mutability: Some(Default::default()),
-                    elem: Box::new(Type::Path(TypePath {
-                        qself: None,
-                        path: Path::new("Self").to_syn_path(),
-                    })),
-                })))
-            }
-        };
-        let item = ImplItem::Fn(ImplItemFn {
-            attrs: vec![],
-            vis: Visibility::Public(Default::default()),
+        let mut attributes = self.generate_doc_attributes();
+        attributes.append(&mut self.cfg_attributes());
+        attributes.append(&mut self.deprecated_attributes());
+        ImplItem::Fn(ImplItemFn {
+            attrs: attributes,
+            vis: self.visibility.clone(),
             defaultness: None,
             sig: Signature {
                 constness: None,
@@ -198,20 +635,71 @@ impl Field {
                 ident,
                 generics: Default::default(),
                 paren_token: Default::default(),
-                inputs: arguments,
+                inputs,
                 variadic: None,
-                output: return_type,
+                output: ReturnType::Type(Default::default(), Box::new(Type::Reference(TypeReference {
+                    and_token: Default::default(),
+                    lifetime: None,
+                    mutability: Some(Default::default()),
+                    elem: Box::new(Type::Path(TypePath {
+                        qself: None,
+                        path: return_type.to_syn_path(),
+                    })),
+                }))),
             },
             block: Block {
                 brace_token: Default::default(),
-                stmts: self.generate_set_method_statements(),
+                stmts: statements,
             },
-        });
-        item
+        })
+    }
+
+    pub(self) fn collection_segment(&self) -> Option<&FieldTypeSegment> {
+        let segment = self.ty.target();
+        match &segment.inner {
+            InnerFieldTypeSegment::Vec(_) |
+            InnerFieldTypeSegment::Set(_) |
+            InnerFieldTypeSegment::Map(_) => Some(segment),
+            _ => None
+        }
+    }
+
+    pub(self) fn generate_init_collection_statement(ident: &String, segment: &FieldTypeSegment) -> Stmt {
+        let collection = match &segment.inner {
+            InnerFieldTypeSegment::Vec(_) => Path::new("Vec"),
+            InnerFieldTypeSegment::Set(value) => Path::new(value.ty.last().unwrap().ident.to_string()),
+            InnerFieldTypeSegment::Map(_) => Path::new("HashMap"),
+            _ => panic!("Expected collection type")
+        };
+        Statement::let_none_condition(
+            Expr::Stmt(Statement::access_field(
+                Path::new("self"),
+                Path::new(ident.clone())
+            )),
+            vec![
+                Statement::assign_field(
+                    Path::new("self"),
+                    Path::new(ident.clone()),
+                    Expr::Stmt(Statement::call(
+                        Path::new("Some"),
+                        vec![
+                            Expr::Stmt(Statement::call(
+                                collection.join("new").clone(),
+                                vec![]
+                            ))
+                        ]
+                    ))
+                )
+            ]
+        )
     }
 
     pub(self) fn generate_set_method_arguments(&self) -> Vec<(Ident, Path)> {
-        match &self.ty.inner {
+        Self::generate_segment_arguments(&self.ty)
+    }
+
+    pub(self) fn generate_segment_arguments(segment: &FieldTypeSegment) -> Vec<(Ident, Path)> {
+        match &segment.inner {
             InnerFieldTypeSegment::Complex(_) => vec![],
             InnerFieldTypeSegment::Vec(value) => {
                 vec![
@@ -221,6 +709,14 @@ impl Field {
                     )
                 ]
             }
+            InnerFieldTypeSegment::Set(value) => {
+                vec![
+                    (
+                        create_ident("value"),
+                        value.item.unwrap()
+                    )
+                ]
+            }
             InnerFieldTypeSegment::Map(value) => {
                 vec![
                     (
@@ -234,23 +730,13 @@ impl Field {
                 ]
             }
             InnerFieldTypeSegment::Remap(value) => {
-                vec![
-                    (
-                        create_ident("value"),
-                        match &(*value.target).inner {
-                            InnerFieldTypeSegment::Vec(value) => {
-                                value.item.unwrap()
-                            }
-                            _ => value.target.unwrap()
-                        }
-                    )
-                ]
+                Self::generate_segment_arguments(&value.target)
             }
             _ => {
                 vec![
                     (
                         create_ident("value"),
-                        self.ty.unwrap()
+                        segment.unwrap()
                     )
                 ]
             }
@@ -281,7 +767,8 @@ impl Field {
         }
         fn statement_generator(
             ident: &String,
-            segment: &FieldTypeSegment
+            segment: &FieldTypeSegment,
+            naming: &Naming
         ) -> Vec<Stmt> {
             match &segment.inner {
                 InnerFieldTypeSegment::Complex(_) => {
@@ -299,7 +786,7 @@ impl Field {
                                         Path::new("Some"),
                                         vec![
                                             Expr::Stmt(Statement::call(
-                                                segment.unwrap().join("new").clone(),
+                                                segment.unwrap().join(naming.new_method.as_str()).clone(),
                                                 vec![]
                                             ))
                                         ]
@@ -347,6 +834,21 @@ impl Field {
                         Statement::implicit_return(Expr::Path(Path::new("self")))
                     ]
                 }
+                InnerFieldTypeSegment::Set(_) => {
+                    vec![
+                        Field::generate_init_collection_statement(ident, segment),
+                        Statement::method_chain_call(extend_chain_call(
+                            &chain_call(ident.clone()),
+                            ExprMethodChainCall::Chained {
+                                method: Path::new("insert"),
+                                arguments: vec![
+                                    Expr::Path(Path::new("value"))
+                                ],
+                            }
+                        )),
+                        Statement::implicit_return(Expr::Path(Path::new("self")))
+                    ]
+                }
                 InnerFieldTypeSegment::Map(_) => {
                     vec![
                         Statement::let_none_condition(
@@ -384,7 +886,7 @@ impl Field {
                     ]
                 }
                 InnerFieldTypeSegment::Remap(value) => {
-                    statement_generator(ident, &*value.target)
+                    statement_generator(ident, &*value.target, naming)
                 }
                 _ => {
                     vec![
@@ -403,20 +905,95 @@ impl Field {
                 }
             }   
         }
-        statement_generator(&self.ident, &self.ty)
+        match self.stores_explicit_none() {
+            true => {
+                vec![
+                    Statement::assign_field(
+                        Path::new("self"),
+                        Path::new(self.ident.clone()),
+                        Expr::Stmt(Statement::call(
+                            Path::new("Some"),
+                            vec![
+                                Expr::Stmt(Statement::call(
+                                    Path::new("Some"),
+                                    vec![
+                                        Expr::Path(Path::new("value"))
+                                    ]
+                                ))
+                            ]
+                        ))
+                    ),
+                    Statement::implicit_return(Expr::Path(Path::new("self")))
+                ]
+            }
+            false => statement_generator(&self.ident, &self.ty, &self.generator.naming())
+        }
     }
 
     pub(crate) fn generate_build_method_statement(&self) -> Stmt {
+        self.generate_build_statement(false)
+    }
+
+    pub(crate) fn generate_try_build_method_statement(&self) -> Stmt {
+        self.generate_build_statement(true)
+    }
+
+    pub(self) fn generate_build_statement(&self, is_fallible: bool) -> Stmt {
+        if self.is_skipped {
+            return Stmt::Expr(self.default.clone().unwrap(), None);
+        }
+        fn into_generator(segment: &FieldTypeSegment, expr: syn::Expr) -> syn::Expr {
+            match &segment.inner {
+                InnerFieldTypeSegment::Option(value) => into_generator(&value.underlying_ty, expr),
+                InnerFieldTypeSegment::Generic(value) => {
+                    let ty = create_ident(value.ty.last().unwrap().ident.to_string());
+                    let expr = into_generator(&value.underlying_ty, expr);
+                    parse_quote!(#ty::new(#expr))
+                }
+                _ => parse_quote!(#expr.into())
+            }
+        }
+        fn converter_generator(
+            ident: &String,
+            converter: &FieldConverter,
+            is_fallible: bool
+        ) -> Expr {
+            let call: syn::Expr = match &converter.expr {
+                syn::Expr::Closure(value) => parse_quote!((#value)(value)),
+                value => parse_quote!(#value(value))
+            };
+            let message = format!("field '{}' conversion failed: {{}}", ident);
+            let expr: syn::Expr = match (converter.is_fallible, is_fallible) {
+                (false, _) => call,
+                (true, false) => parse_quote!(#call.unwrap_or_else(|error| panic!(#message, error))),
+                (true, true) => parse_quote!(#call.map_err(|error| format!(#message, error))?)
+            };
+            Expr::Stmt(Stmt::Expr(expr, None))
+        }
         fn statement_generator(
-            segment: &FieldTypeSegment
+            ident: &String,
+            segment: &FieldTypeSegment,
+            naming: &Naming,
+            is_fallible: bool
         ) -> Expr {
             match &segment.inner {
-                InnerFieldTypeSegment::Complex(_) => {
-                    Expr::Stmt(Statement::method_call(
-                        Expr::Path(Path::new("value")),
-                        Path::new("build"),
-                        vec![]
-                    ))
+                InnerFieldTypeSegment::Complex(value) => {
+                    match is_fallible && Field::has_fallible_fields(&value.inner) {
+                        true => {
+                            let method = create_ident(&naming.try_build_method);
+                            Expr::Stmt(Stmt::Expr(parse_quote!(value.#method()?), None))
+                        }
+                        false => {
+                            Expr::Stmt(Statement::method_call(
+                                Expr::Path(Path::new("value")),
+                                Path::new(naming.build_method.clone()),
+                                vec![]
+                            ))
+                        }
+                    }
+                }
+                InnerFieldTypeSegment::Remap(value) if value.converter.is_some() => {
+                    converter_generator(ident, value.converter.as_ref().unwrap(), is_fallible)
                 }
                 InnerFieldTypeSegment::Remap(value) => {
                     match (&(*value.source).inner, &(*value.target).inner) {
@@ -455,14 +1032,6 @@ impl Field {
                                 ]
                             ))
                         }
-                        (InnerFieldTypeSegment::Reserved(_), InnerFieldTypeSegment::Reserved(_)) |
-                        (InnerFieldTypeSegment::Reserved(_), InnerFieldTypeSegment::Vec(_)) => {
-                            Expr::Stmt(Statement::method_call(
-                                Expr::Path(Path::new("value")),
-                                Path::new("into"),
-                                vec![]
-                            ))
-                        }
                         (InnerFieldTypeSegment::Option(_), InnerFieldTypeSegment::Vec(_)) => {
                             Expr::Stmt(Statement::method_chain_call(
                                 vec![
@@ -498,15 +1067,96 @@ impl Field {
                                 ]
                             ))
                         }
-                        _ => panic!("Remapped field not supported: {:?}", value)
+                        (InnerFieldTypeSegment::Vec(_), InnerFieldTypeSegment::Set(_)) |
+                        (InnerFieldTypeSegment::Set(_), InnerFieldTypeSegment::Vec(_)) |
+                        (InnerFieldTypeSegment::Set(_), InnerFieldTypeSegment::Set(_)) |
+                        (InnerFieldTypeSegment::Option(_), InnerFieldTypeSegment::Set(_)) => {
+                            Expr::Stmt(Statement::method_chain_call(
+                                vec![
+                                    ExprMethodChainCall::Start {
+                                        receiver: Expr::Path(Path::new("value")),
+                                        method: Path::new("into_iter"),
+                                        arguments: vec![],
+                                    },
+                                    ExprMethodChainCall::Chained {
+                                        method: Path::new("map"),
+                                        arguments: vec![
+                                            Expr::Stmt(Statement::closure(
+                                                vec![Pat::Ident(PatIdent {
+                                                    attrs: vec![],
+                                                    by_ref: None,
+                                                    mutability: None,
+                                                    ident: create_ident("value"),
+                                                    subpat: None,
+                                                })],
+                                                ReturnType::Default,
+                                                Expr::Stmt(Statement::method_call(
+                                                    Expr::Path(Path::new("value")),
+                                                    Path::new("into"),
+                                                    vec![]
+                                                ))
+                                            ))
+                                        ],
+                                    },
+                                    ExprMethodChainCall::Chained {
+                                        method: Path::new("collect"),
+                                        arguments: vec![],
+                                    }
+                                ]
+                            ))
+                        }
+                        (InnerFieldTypeSegment::Map(_), InnerFieldTypeSegment::Map(_)) => {
+                            Expr::Stmt(Stmt::Expr(
+                                parse_quote!(value.into_iter().map(|(key, value)| (key.into(), value.into())).collect()),
+                                None
+                            ))
+                        }
+                        (
+                            InnerFieldTypeSegment::Reserved(_) |
+                            InnerFieldTypeSegment::Complex(_) |
+                            InnerFieldTypeSegment::Option(_) |
+                            InnerFieldTypeSegment::Generic(_),
+                            InnerFieldTypeSegment::Complex(target)
+                        ) => {
+                            let expr: syn::Expr = match is_fallible && Field::has_fallible_fields(&target.inner) {
+                                true => {
+                                    let method = create_ident(&naming.try_build_method);
+                                    parse_quote!(value.#method()?)
+                                }
+                                false => {
+                                    let method = create_ident(&naming.build_method);
+                                    parse_quote!(value.#method())
+                                }
+                            };
+                            Expr::Stmt(Stmt::Expr(into_generator(&value.source, expr), None))
+                        }
+                        (
+                            InnerFieldTypeSegment::Reserved(_) |
+                            InnerFieldTypeSegment::Complex(_) |
+                            InnerFieldTypeSegment::Option(_) |
+                            InnerFieldTypeSegment::Generic(_),
+                            InnerFieldTypeSegment::Reserved(_)
+                        ) |
+                        (InnerFieldTypeSegment::Reserved(_), InnerFieldTypeSegment::Vec(_)) |
+                        (InnerFieldTypeSegment::Option(_), InnerFieldTypeSegment::Option(_)) => {
+                            Expr::Stmt(Stmt::Expr(into_generator(&value.source, parse_quote!(value)), None))
+                        }
+                        (source, target) => {
+                            panic!(
+                                "Remapping field '{}' from '{}' to '{}' is not supported",
+                                ident,
+                                source,
+                                target
+                            )
+                        }
                     }
                 }
                 _ => {
                     Expr::Path(Path::new("value"))
                 }
             }
-        };
-        let call = statement_generator(&self.ty);
+        }
+        let call = statement_generator(&self.ident, &self.ty, &self.generator.naming(), is_fallible);
         Statement::let_some_condition(
             Expr::Stmt(Statement::method_call(
                 Expr::Stmt(Statement::access_field(
@@ -518,11 +1168,8 @@ impl Field {
             )),
             Path::new("value"),
             vec![Statement::implicit_return(
-                match self.is_required {
+                match self.is_optional_type && (!self.is_required || self.ty.is_nested_option()) {
                     true => {
-                        call
-                    }
-                    false => {
                         Expr::Stmt(Statement::call(
                             Path::new("Some").clone(),
                             vec![
@@ -530,16 +1177,42 @@ impl Field {
                             ]
                         ))
                     }
+                    false => {
+                        call
+                    }
                 }
             )],
-            match self.is_required {
-                true => {
-                    Some(Expr::Stmt(Statement::panic(
-                        format!("field '{}' is required", self.ident),
-                        vec![]
+            match (self.is_required, self.is_optional_type) {
+                (true, _) => {
+                    let message = format!("field '{}' is required", self.ident);
+                    match is_fallible {
+                        true => {
+                            Some(Expr::Stmt(Stmt::Expr(
+                                parse_quote!(return Err(String::from(#message))),
+                                Some(Default::default())
+                            )))
+                        }
+                        false => {
+                            Some(Expr::Stmt(Statement::panic(
+                                message,
+                                vec![]
+                            )))
+                        }
+                    }
+                }
+                (false, true) if self.default.is_some() => {
+                    Some(Expr::Stmt(Statement::implicit_return(
+                        Expr::Stmt(Statement::block(vec![
+                            Statement::without_trailling_semi_colon(
+                                Statement::call(
+                                    Path::new("Some"),
+                                    vec![Expr::Stmt(Stmt::Expr(self.default.clone().unwrap(), None))]
+                                )
+                            )
+                        ]))
                     )))
                 }
-                false => {
+                (false, true) => {
                     Some(Expr::Stmt(Statement::implicit_return(
                         Expr::Stmt(Statement::block(vec![
                             Statement::without_trailling_semi_colon(
@@ -548,6 +1221,15 @@ impl Field {
                         ]))
                     )))
                 }
+                (false, false) => {
+                    let default = self.default.clone()
+                        .unwrap_or(parse_quote!(Default::default()));
+                    Some(Expr::Stmt(Statement::implicit_return(
+                        Expr::Stmt(Statement::block(vec![
+                            Stmt::Expr(default, None)
+                        ]))
+                    )))
+                }
             }
         )
     }
@@ -1,11 +1,18 @@
+use crate::constants::WRAPPER_TYPES;
 use crate::field::Field;
+use crate::field_rule_selector::FieldRuleSelector;
+use crate::field_type_segment::FieldConverter;
+use crate::ident::validate_ident;
+use crate::rule_error::RuleError;
+use crate::rule_report::{RuleMatch, RuleReportEntry};
 use ast_shaper::utils::create_generic_type;
 use ast_shaper::utils::path::Path;
-use quote::ToTokens;
 use std::cell::RefCell;
 use std::fmt::{Debug, Formatter};
 use std::rc::Rc;
-use syn::{parse_str, Meta};
+use quote::quote;
+use syn::punctuated::Punctuated;
+use syn::{parse_str, Attribute, Expr, GenericArgument, Meta, MetaList, Pat, PatType, PathArguments, Token, Type, TypePath, Visibility};
 
 pub struct FieldRuleItemSelectorBuilder {
     rules: Rc<RefCell<Vec<FieldRule>>>
@@ -19,228 +26,486 @@ impl FieldRuleItemSelectorBuilder {
     }
 
     pub fn for_all(&self) -> FieldRuleFieldSelectorBuilder {
-        FieldRuleFieldSelectorBuilder::new(self.rules.clone(), None)
+        self.for_items(FieldRuleSelector::all())
     }
 
     pub fn for_item(&self, ident: impl Into<String>) -> FieldRuleFieldSelectorBuilder {
-        FieldRuleFieldSelectorBuilder::new(self.rules.clone(), Some(ident.into()))
+        self.for_items(FieldRuleSelector::item(ident))
+    }
+
+    pub fn for_items_matching(&self, pattern: impl Into<String>) -> FieldRuleFieldSelectorBuilder {
+        self.for_items(FieldRuleSelector::items_matching(pattern))
+    }
+
+    pub fn try_for_items_matching(&self, pattern: impl Into<String>) -> Result<FieldRuleFieldSelectorBuilder, RuleError> {
+        Ok(self.for_items(FieldRuleSelector::try_items_matching(pattern)?))
+    }
+
+    pub fn for_items_matching_regex(&self, pattern: impl Into<String>) -> FieldRuleFieldSelectorBuilder {
+        self.for_items(FieldRuleSelector::items_matching_regex(pattern))
+    }
+
+    pub fn try_for_items_matching_regex(&self, pattern: impl Into<String>) -> Result<FieldRuleFieldSelectorBuilder, RuleError> {
+        Ok(self.for_items(FieldRuleSelector::try_items_matching_regex(pattern)?))
+    }
+
+    pub fn for_items_with_attribute(&self, attribute: impl Into<String>) -> FieldRuleFieldSelectorBuilder {
+        self.for_items(FieldRuleSelector::item_attribute(attribute))
+    }
+
+    pub fn for_items(&self, selector: FieldRuleSelector) -> FieldRuleFieldSelectorBuilder {
+        FieldRuleFieldSelectorBuilder::new(self.rules.clone(), selector)
     }
 }
 
 pub struct FieldRuleFieldSelectorBuilder {
     rules: Rc<RefCell<Vec<FieldRule>>>,
-    item_ident: Option<String>
+    item_selector: FieldRuleSelector
 }
 
 impl FieldRuleFieldSelectorBuilder {
     pub(self) fn new(
         rules: Rc<RefCell<Vec<FieldRule>>>,
-        item_ident: Option<String>
+        item_selector: FieldRuleSelector
     ) -> Self {
         Self {
             rules,
-            item_ident,
+            item_selector,
         }
     }
 
     pub fn and_all_fields(&mut self) -> FieldRuleThenSelectorBuilder {
-        FieldRuleThenSelectorBuilder::new(
-            self.rules.clone(), 
-            self.item_ident.clone(), 
-            None,
-            None
-        )
+        self.with_fields(FieldRuleSelector::all())
     }
 
     pub fn with_field_ident(&mut self, ident: impl Into<String>) -> FieldRuleThenSelectorBuilder {
-        FieldRuleThenSelectorBuilder::new(
-            self.rules.clone(), 
-            self.item_ident.clone(), 
-            Some(ident.into()),
-            None
-        )
+        self.with_fields(FieldRuleSelector::field_ident(ident))
+    }
+
+    pub fn with_field_ident_matching(&mut self, pattern: impl Into<String>) -> FieldRuleThenSelectorBuilder {
+        self.with_fields(FieldRuleSelector::field_ident_matching(pattern))
+    }
+
+    pub fn try_with_field_ident_matching(&mut self, pattern: impl Into<String>) -> Result<FieldRuleThenSelectorBuilder, RuleError> {
+        Ok(self.with_fields(FieldRuleSelector::try_field_ident_matching(pattern)?))
+    }
+
+    pub fn with_field_ident_matching_regex(&mut self, pattern: impl Into<String>) -> FieldRuleThenSelectorBuilder {
+        self.with_fields(FieldRuleSelector::field_ident_matching_regex(pattern))
+    }
+
+    pub fn try_with_field_ident_matching_regex(&mut self, pattern: impl Into<String>) -> Result<FieldRuleThenSelectorBuilder, RuleError> {
+        Ok(self.with_fields(FieldRuleSelector::try_field_ident_matching_regex(pattern)?))
     }
 
     pub fn with_field_type(&mut self, ty: impl Into<String>) -> FieldRuleThenSelectorBuilder {
+        self.with_fields(FieldRuleSelector::field_type(ty))
+    }
+
+    pub fn try_with_field_type(&mut self, ty: impl Into<String>) -> Result<FieldRuleThenSelectorBuilder, RuleError> {
+        Ok(self.with_fields(FieldRuleSelector::try_field_type(ty)?))
+    }
+
+    pub fn with_field_attribute(&mut self, attribute: impl Into<String>) -> FieldRuleThenSelectorBuilder {
+        self.with_fields(FieldRuleSelector::field_attribute(attribute))
+    }
+
+    pub fn with_fields(&mut self, selector: FieldRuleSelector) -> FieldRuleThenSelectorBuilder {
         FieldRuleThenSelectorBuilder::new(
-            self.rules.clone(), 
-            self.item_ident.clone(), 
-            None,
-            Some(Path::new(ty.into()))
+            self.rules.clone(),
+            self.item_selector.clone().and(selector)
         )
     }
 }
 
 pub struct FieldRuleThenSelectorBuilder {
     rules: Rc<RefCell<Vec<FieldRule>>>,
-    item_ident: Option<String>,
-    field_ident: Option<String>,
-    field_type: Option<Path>,
+    selector: FieldRuleSelector,
 }
 
 impl FieldRuleThenSelectorBuilder {
     pub(self) fn new(
         rules: Rc<RefCell<Vec<FieldRule>>>,
-        item_ident: Option<String>,
-        field_ident: Option<String>,
-        field_type: Option<Path>
+        selector: FieldRuleSelector
     ) -> Self {
         Self {
             rules,
-            item_ident,
-            field_ident,
-            field_type,
+            selector,
         }
     }
 
-    pub(self) fn then<F>(&mut self, rule: F) -> &mut Self
+    pub fn except_field(&mut self, ident: impl Into<String>) -> &mut Self {
+        self.except(FieldRuleSelector::field_ident(ident))
+    }
+
+    pub fn except(&mut self, selector: FieldRuleSelector) -> &mut Self {
+        self.selector = self.selector.clone().and(!selector);
+        self
+    }
+
+    pub(self) fn then<F>(&mut self, description: String, rule: F) -> &mut Self
     where
         F: Fn(&mut Field) + 'static
     {
         let rule = FieldRule::new(
-            self.item_ident.clone(),
-            self.field_ident.clone(),
-            self.field_type.clone(),
+            self.selector.clone(),
+            description,
             rule,
         );
         self.rules.borrow_mut().push(rule);
         self
     }
 
+    pub(self) fn ensure_specific_fields(&self, action: &str) -> Result<(), RuleError> {
+        match self.selector.targets_specific_fields() {
+            true => Ok(()),
+            false => Err(RuleError::SelectorTargetsAllFields {
+                action: action.to_string(),
+                selector: self.selector.to_string(),
+            })
+        }
+    }
+
     pub fn then_map(&mut self, ty: Path) -> &mut Self {
-        if self.field_ident.is_none() && self.field_type.is_none() {
-            panic!("Cannot remap field when field selector target all field");
+        self.try_then_map(ty).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_then_map(&mut self, ty: Path) -> Result<&mut Self, RuleError> {
+        self.ensure_specific_fields("remap")?;
+        Self::validate_remap_target(&ty)?;
+        Ok(self.then(format!("map({})", ty), move |field| field.map(ty.clone())))
+    }
+
+    pub(self) fn validate_remap_target(ty: &Path) -> Result<(), RuleError> {
+        fn validate(path: &syn::Path) -> Result<(), String> {
+            for segment in path.segments.iter() {
+                let arguments = match &segment.arguments {
+                    PathArguments::AngleBracketed(value) => value.args.iter()
+                        .filter_map(|argument| match argument {
+                            GenericArgument::Type(Type::Path(value)) => Some(&value.path),
+                            _ => None
+                        })
+                        .collect::<Vec<_>>(),
+                    _ => Vec::new()
+                };
+                let ident = segment.ident.to_string();
+                let expected = match ident.as_str() {
+                    "Vec" | "HashSet" | "BTreeSet" | "Option" => 1,
+                    "HashMap" => 2,
+                    _ => arguments.len()
+                };
+                if arguments.len() != expected {
+                    return Err(format!("'{}' expects {} type argument(s)", ident, expected));
+                }
+                for argument in arguments.into_iter() {
+                    validate(argument)?;
+                }
+            }
+            Ok(())
         }
-        self.then(move |field| field.map(ty.clone()))
+        let ident = ty.last().unwrap().ident.to_string();
+        let result = match WRAPPER_TYPES.contains(&ident.as_str()) {
+            true => Err(format!("remapping to '{}' is not supported, use a converter instead", ident)),
+            false => validate(&ty.to_syn_path())
+        };
+        result.map_err(|message| RuleError::InvalidType {
+            ty: ty.to_string(),
+            message,
+        })
     }
 
     pub fn then_map_to_vec(&mut self, ty: Path) -> &mut Self {
-        if self.field_ident.is_none() && self.field_type.is_none() {
-            panic!("Cannot remap field when field selector target all field");
-        }
-        self.then(move |field| {
+        self.try_then_map_to_vec(ty).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_then_map_to_vec(&mut self, ty: Path) -> Result<&mut Self, RuleError> {
+        self.ensure_specific_fields("remap")?;
+        Self::validate_remap_target(&create_generic_type("Vec", vec![ty.clone()]))?;
+        Ok(self.then(format!("map_to_vec({})", ty), move |field| {
             field.map(create_generic_type("Vec", vec![ty.clone()]))
-        })
+        }))
+    }
+
+    pub fn then_map_with(&mut self, ty: Path, converter: impl Into<String>) -> &mut Self {
+        self.try_then_map_with(ty, converter).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_then_map_with(&mut self, ty: Path, converter: impl Into<String>) -> Result<&mut Self, RuleError> {
+        self.then_map_with_converter(ty, converter.into(), false)
+    }
+
+    pub fn then_try_map_with(&mut self, ty: Path, converter: impl Into<String>) -> &mut Self {
+        self.try_then_try_map_with(ty, converter).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_then_try_map_with(&mut self, ty: Path, converter: impl Into<String>) -> Result<&mut Self, RuleError> {
+        self.then_map_with_converter(ty, converter.into(), true)
+    }
+
+    pub(self) fn then_map_with_converter(
+        &mut self,
+        ty: Path,
+        converter: String,
+        is_fallible: bool
+    ) -> Result<&mut Self, RuleError> {
+        self.ensure_specific_fields("remap")?;
+        let description = match is_fallible {
+            true => format!("try_map_with({}, {})", ty, converter),
+            false => format!("map_with({}, {})", ty, converter)
+        };
+        let expr = match parse_str::<Expr>(converter.as_str()) {
+            Ok(Expr::Path(value)) => Expr::Path(value),
+            Ok(Expr::Closure(mut value)) => {
+                for input in value.inputs.iter_mut() {
+                    if let Pat::Type(_) = input {
+                        continue;
+                    }
+                    *input = Pat::Type(PatType {
+                        attrs: vec![],
+                        pat: Box::new(input.clone()),
+                        colon_token: Default::default(),
+                        ty: Box::new(Type::Path(TypePath {
+                            qself: None,
+                            path: ty.to_syn_path(),
+                        })),
+                    });
+                }
+                Expr::Closure(value)
+            }
+            Ok(_) => return Err(RuleError::InvalidConverter {
+                converter,
+                message: "expected a function path or a closure".to_string(),
+            }),
+            Err(error) => return Err(RuleError::InvalidConverter {
+                converter,
+                message: error.to_string(),
+            })
+        };
+        let converter = FieldConverter {
+            expr,
+            is_fallible,
+        };
+        Ok(self.then(description, move |field| field.map_with(ty.clone(), converter.clone())))
     }
 
     pub fn then_rename(&mut self, ident: impl Into<String>) -> &mut Self {
-        if self.field_ident.is_none() && self.field_type.is_none() {
-            panic!("Cannot rename field when field selector target all field");
-        }
+        self.try_then_rename(ident).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_then_rename(&mut self, ident: impl Into<String>) -> Result<&mut Self, RuleError> {
+        self.ensure_specific_fields("rename")?;
         let ident = ident.into();
-        self.then(move |field| field.rename(ident.clone()))
+        validate_ident(&ident)?;
+        Ok(self.then(format!("rename({})", ident), move |field| field.rename(ident.clone())))
+    }
+
+    pub fn then_skip(&mut self, default: impl Into<String>) -> &mut Self {
+        self.try_then_skip(default).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_then_skip(&mut self, default: impl Into<String>) -> Result<&mut Self, RuleError> {
+        let default = default.into();
+        let description = format!("skip({})", default);
+        let source = default.trim().to_string();
+        let default = match parse_str::<Expr>(default.as_str()) {
+            Ok(value) => value,
+            Err(error) => return Err(RuleError::InvalidDefault {
+                default,
+                message: error.to_string(),
+            })
+        };
+        Ok(self.then(description, move |field| {
+            field.skip(default.clone());
+            field.default_source = Some(source.clone());
+        }))
+    }
+
+    pub fn then_default(&mut self, default: impl Into<String>) -> &mut Self {
+        self.try_then_default(default).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_then_default(&mut self, default: impl Into<String>) -> Result<&mut Self, RuleError> {
+        let default = default.into();
+        let description = format!("default({})", default);
+        let source = default.trim().to_string();
+        let default = match parse_str::<Expr>(default.as_str()) {
+            Ok(value) => value,
+            Err(error) => return Err(RuleError::InvalidDefault {
+                default,
+                message: error.to_string(),
+            })
+        };
+        Ok(self.then(description, move |field| {
+            field.set_default(default.clone());
+            field.default_source = Some(source.clone());
+        }))
+    }
+
+    pub fn then_visibility(&mut self, visibility: impl Into<String>) -> &mut Self {
+        self.try_then_visibility(visibility).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_then_visibility(&mut self, visibility: impl Into<String>) -> Result<&mut Self, RuleError> {
+        let visibility = visibility.into();
+        let description = format!("visibility({})", visibility);
+        let visibility = match parse_str::<Visibility>(visibility.as_str()) {
+            Ok(value) => value,
+            Err(error) => return Err(RuleError::InvalidVisibility {
+                visibility,
+                message: error.to_string(),
+            })
+        };
+        Ok(self.then(description, move |field| field.set_visibility(visibility.clone())))
+    }
+
+    pub fn then_required(&mut self) -> &mut Self {
+        self.then("required".to_string(), move |field| field.set_required(true))
+    }
+
+    pub fn try_then_required(&mut self) -> Result<&mut Self, RuleError> {
+        Ok(self.then_required())
+    }
+
+    pub fn then_optional(&mut self) -> &mut Self {
+        self.then("optional".to_string(), move |field| field.set_required(false))
+    }
+
+    pub fn try_then_optional(&mut self) -> Result<&mut Self, RuleError> {
+        Ok(self.then_optional())
     }
 
     pub fn then_discard_attribute(&mut self, attribute: impl Into<String>) -> &mut Self {
         let attribute_to_compare = attribute.into();
-        self.then(move |field| {
-            field.attributes_mut().retain_mut(|attribute| {
-                match &attribute.meta {
-                    Meta::Path(value) => {
-                        let attribute_to_compare = parse_str(attribute_to_compare.as_str())
-                            .map(Meta::Path);
-                        if attribute_to_compare.is_err() {
-                            return true;
-                        }
-                        let attribute_to_compare = attribute_to_compare
-                            .as_ref()
-                            .unwrap()
-                            .require_path_only()
-                            .unwrap();
-                        value != attribute_to_compare
-                    },
-                    Meta::NameValue(value) => {
-                        let attribute_to_compare = parse_str(attribute_to_compare.as_str())
-                            .map(Meta::NameValue);
-                        if attribute_to_compare.is_err() {
-                            return true;
-                        }
-                        let attribute_to_compare = attribute_to_compare
-                            .as_ref()
-                            .unwrap()
-                            .require_name_value()
-                            .unwrap();
-                        value != attribute_to_compare
-                    },
-                    Meta::List(value) => {
-                        let attribute_to_compare = parse_str(attribute_to_compare.as_str())
-                            .map(Meta::List);
-                        if attribute_to_compare.is_err() {
-                            return true;
-                        }
-                        let attribute_to_compare = attribute_to_compare
-                            .as_ref()
-                            .unwrap()
-                            .require_list()
-                            .unwrap();
-                        let l = attribute_to_compare.to_token_stream().to_string();
-                        let l = value.to_token_stream().to_string();
-                        value != attribute_to_compare
-                    }
-                }
-            });
+        let description = format!("discard_attribute({})", attribute_to_compare);
+        self.then(description, move |field| {
+            let attributes = discard_attribute(field.attributes_mut(), attribute_to_compare.as_str());
+            *field.attributes_mut() = attributes;
         })
     }
+
+    pub fn try_then_discard_attribute(&mut self, attribute: impl Into<String>) -> Result<&mut Self, RuleError> {
+        Ok(self.then_discard_attribute(attribute))
+    }
+}
+
+pub(crate) fn compare_attribute(attribute: &Attribute, attribute_to_compare: &str) -> bool {
+    let attribute_to_compare = match parse_str::<Meta>(attribute_to_compare) {
+        Ok(value) => value,
+        Err(_) => return false
+    };
+    match (&attribute.meta, &attribute_to_compare) {
+        (_, Meta::Path(path)) => attribute.path() == path,
+        (Meta::List(value), Meta::List(other_value)) if value.path == other_value.path => {
+            match (list_entries(value), list_entries(other_value)) {
+                (Some(entries), Some(other_entries)) => {
+                    other_entries.iter().all(|entry| entries.contains(entry))
+                }
+                _ => value == other_value
+            }
+        }
+        (value, other_value) => value == other_value
+    }
+}
+
+pub(crate) fn discard_attribute(attributes: &[Attribute], attribute_to_discard: &str) -> Vec<Attribute> {
+    attributes.iter()
+        .filter_map(|attribute| {
+            if !compare_attribute(attribute, attribute_to_discard) {
+                return Some(attribute.clone());
+            }
+            let (value, other_value) = match (&attribute.meta, parse_str::<Meta>(attribute_to_discard)) {
+                (Meta::List(value), Ok(Meta::List(other_value))) => (value, other_value),
+                _ => return None
+            };
+            let entries = list_entries(value)?;
+            let other_entries = list_entries(&other_value)?;
+            let entries = entries.into_iter()
+                .filter(|entry| !other_entries.contains(entry))
+                .collect::<Vec<_>>();
+            if entries.is_empty() {
+                return None;
+            }
+            let mut attribute = attribute.clone();
+            let mut list = value.clone();
+            list.tokens = quote!(#(#entries),*);
+            attribute.meta = Meta::List(list);
+            Some(attribute)
+        })
+        .collect()
+}
+
+fn list_entries(list: &MetaList) -> Option<Vec<Meta>> {
+    list.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+        .ok()
+        .map(|entries| entries.into_iter().collect())
 }
 
 pub struct FieldRule {
-    item_ident: Option<String>,
-    field_ident: Option<String>,
-    field_type: Option<Path>,
+    selector: FieldRuleSelector,
+    description: String,
+    matches: RefCell<Vec<RuleMatch>>,
     rule: Box<dyn Fn(&mut Field) + 'static>
 }
 
 impl FieldRule {
     pub(self) fn new(
-        item_ident: Option<String>,
-        field_ident: Option<String>,
-        field_type: Option<Path>,
+        selector: FieldRuleSelector,
+        description: String,
         rule: impl Fn(&mut Field) + 'static
-
     ) -> Self {
         Self {
-            item_ident,
-            field_ident,
-            field_type,
+            selector,
+            description,
+            matches: RefCell::new(Vec::new()),
             rule: Box::new(rule),
         }
     }
 
-    pub(crate) fn apply(&self, item_ident: &String, field: &mut Field) {
-        match self.item_ident.as_ref() {
-            Some(value) => {
-                if value != item_ident {
-                    return
-                }
-            }
-            None => {}
-        }
-        match self.field_ident.as_ref() {
-            Some(value) => {
-                if value != &field.ident {
-                    return
-                }
+    pub(crate) fn merge(rules: Vec<FieldRule>) -> Option<Self> {
+        let selector = rules.first()?.selector.clone();
+        let description = rules.iter()
+            .map(|rule| rule.description.clone())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let actions = rules.into_iter()
+            .map(|rule| rule.rule)
+            .collect::<Vec<_>>();
+        Some(Self::new(selector, description, move |field| {
+            for action in actions.iter() {
+                action(field);
             }
-            None => {}
+        }))
+    }
+
+    pub(crate) fn apply(&self, item_ident: &String, item_attributes: &[Attribute], field: &mut Field) {
+        if !self.selector.matches(item_ident, item_attributes, field) {
+            return
         }
-        match self.field_type.as_ref() {
-            Some(value) => {
-            if value != &field.ty.unwrap() {
-                
-            }
-            }
-            None => {}
+        let rule_match = RuleMatch {
+            item: item_ident.clone(),
+            field: field.target_ident.clone(),
+        };
+        if !self.matches.borrow().contains(&rule_match) {
+            self.matches.borrow_mut().push(rule_match);
         }
         (self.rule)(field)
     }
+
+    pub(crate) fn report(&self) -> RuleReportEntry {
+        RuleReportEntry {
+            rule: self.description.clone(),
+            selector: self.selector.to_string(),
+            matches: self.matches.borrow().clone(),
+        }
+    }
 }
 
 impl Debug for FieldRule {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("FieldRule")
-            .field("item_ident", &self.item_ident)
-            .field("field_ident", &self.field_ident)
-            .field("field_type", &self.field_type)
+            .field("selector", &self.selector)
+            .field("description", &self.description)
+            .field("matches", &self.matches)
             .field("rule", &"Not debuggable")
             .finish()
     }
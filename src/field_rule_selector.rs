@@ -0,0 +1,214 @@
+use crate::field::Field;
+use crate::field_rule::compare_attribute;
+use crate::field_type_pattern::FieldTypePattern;
+use crate::ident::unraw;
+use crate::ident_pattern::IdentPattern;
+use crate::rule_error::RuleError;
+use std::fmt::{Display, Formatter};
+use std::ops::Not;
+use syn::Attribute;
+
+#[derive(Debug, Clone)]
+pub(crate) enum InnerFieldRuleSelector {
+    All,
+    ItemIdent(IdentPattern),
+    ItemAttribute(String),
+    FieldIdent(IdentPattern),
+    FieldType(FieldTypePattern),
+    FieldAttribute(String),
+    AllOf(Vec<FieldRuleSelector>),
+    AnyOf(Vec<FieldRuleSelector>),
+    Not(Box<FieldRuleSelector>)
+}
+
+#[derive(Debug, Clone)]
+pub struct FieldRuleSelector {
+    pub(crate) inner: InnerFieldRuleSelector
+}
+
+impl FieldRuleSelector {
+    pub(self) fn new(inner: InnerFieldRuleSelector) -> Self {
+        Self {
+            inner,
+        }
+    }
+
+    pub fn all() -> Self {
+        Self::new(InnerFieldRuleSelector::All)
+    }
+
+    pub fn item(ident: impl Into<String>) -> Self {
+        Self::new(InnerFieldRuleSelector::ItemIdent(IdentPattern::exact(ident)))
+    }
+
+    pub fn items_matching(pattern: impl Into<String>) -> Self {
+        Self::try_items_matching(pattern).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_items_matching(pattern: impl Into<String>) -> Result<Self, RuleError> {
+        Ok(Self::new(InnerFieldRuleSelector::ItemIdent(IdentPattern::try_matching(pattern)?)))
+    }
+
+    pub fn items_matching_regex(pattern: impl Into<String>) -> Self {
+        Self::try_items_matching_regex(pattern).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_items_matching_regex(pattern: impl Into<String>) -> Result<Self, RuleError> {
+        Ok(Self::new(InnerFieldRuleSelector::ItemIdent(IdentPattern::try_regex(pattern)?)))
+    }
+
+    pub fn item_attribute(attribute: impl Into<String>) -> Self {
+        Self::new(InnerFieldRuleSelector::ItemAttribute(attribute.into()))
+    }
+
+    pub fn field_ident(ident: impl Into<String>) -> Self {
+        Self::new(InnerFieldRuleSelector::FieldIdent(IdentPattern::exact(ident)))
+    }
+
+    pub fn field_ident_matching(pattern: impl Into<String>) -> Self {
+        Self::try_field_ident_matching(pattern).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_field_ident_matching(pattern: impl Into<String>) -> Result<Self, RuleError> {
+        Ok(Self::new(InnerFieldRuleSelector::FieldIdent(IdentPattern::try_matching(pattern)?)))
+    }
+
+    pub fn field_ident_matching_regex(pattern: impl Into<String>) -> Self {
+        Self::try_field_ident_matching_regex(pattern).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_field_ident_matching_regex(pattern: impl Into<String>) -> Result<Self, RuleError> {
+        Ok(Self::new(InnerFieldRuleSelector::FieldIdent(IdentPattern::try_regex(pattern)?)))
+    }
+
+    pub fn field_type(ty: impl Into<String>) -> Self {
+        Self::try_field_type(ty).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_field_type(ty: impl Into<String>) -> Result<Self, RuleError> {
+        let ty = ty.into();
+        match FieldTypePattern::parse(ty.clone()) {
+            Some(value) => Ok(Self::new(InnerFieldRuleSelector::FieldType(value))),
+            None => Err(RuleError::InvalidPattern {
+                pattern: ty,
+                message: "invalid field type".to_string(),
+            })
+        }
+    }
+
+    pub fn field_attribute(attribute: impl Into<String>) -> Self {
+        Self::new(InnerFieldRuleSelector::FieldAttribute(attribute.into()))
+    }
+
+    pub fn all_of(selectors: Vec<FieldRuleSelector>) -> Self {
+        Self::new(InnerFieldRuleSelector::AllOf(selectors))
+    }
+
+    pub fn any_of(selectors: Vec<FieldRuleSelector>) -> Self {
+        Self::new(InnerFieldRuleSelector::AnyOf(selectors))
+    }
+
+    pub(crate) fn and(self, selector: FieldRuleSelector) -> Self {
+        if let InnerFieldRuleSelector::All = selector.inner {
+            return self;
+        }
+        match self.inner {
+            InnerFieldRuleSelector::All => selector,
+            InnerFieldRuleSelector::AllOf(mut selectors) => {
+                selectors.push(selector);
+                Self::all_of(selectors)
+            }
+            _ => Self::all_of(vec![self, selector])
+        }
+    }
+
+    pub(crate) fn targets_specific_fields(&self) -> bool {
+        match &self.inner {
+            InnerFieldRuleSelector::All |
+            InnerFieldRuleSelector::ItemIdent(_) |
+            InnerFieldRuleSelector::ItemAttribute(_) => false,
+            InnerFieldRuleSelector::FieldIdent(_) |
+            InnerFieldRuleSelector::FieldType(_) |
+            InnerFieldRuleSelector::FieldAttribute(_) => true,
+            InnerFieldRuleSelector::AllOf(selectors) => {
+                selectors.iter().any(|selector| selector.targets_specific_fields())
+            }
+            InnerFieldRuleSelector::AnyOf(selectors) => {
+                !selectors.is_empty() && selectors.iter().all(|selector| selector.targets_specific_fields())
+            }
+            InnerFieldRuleSelector::Not(selector) => selector.targets_specific_fields()
+        }
+    }
+
+    pub(crate) fn matches(&self, item_ident: &String, item_attributes: &[Attribute], field: &Field) -> bool {
+        match &self.inner {
+            InnerFieldRuleSelector::All => true,
+            InnerFieldRuleSelector::ItemIdent(value) => value.matches(item_ident),
+            InnerFieldRuleSelector::ItemAttribute(value) => {
+                item_attributes.iter().any(|attribute| compare_attribute(attribute, value))
+            }
+            InnerFieldRuleSelector::FieldIdent(value) => value.matches(&unraw(&field.target_ident)),
+            InnerFieldRuleSelector::FieldType(value) => value.matches(&field.item.ty),
+            InnerFieldRuleSelector::FieldAttribute(value) => {
+                field.item.attrs.iter().any(|attribute| compare_attribute(attribute, value))
+            }
+            InnerFieldRuleSelector::AllOf(selectors) => {
+                selectors.iter().all(|selector| selector.matches(item_ident, item_attributes, field))
+            }
+            InnerFieldRuleSelector::AnyOf(selectors) => {
+                selectors.iter().any(|selector| selector.matches(item_ident, item_attributes, field))
+            }
+            InnerFieldRuleSelector::Not(selector) => {
+                !selector.matches(item_ident, item_attributes, field)
+            }
+        }
+    }
+}
+
+impl Not for FieldRuleSelector {
+    type Output = Self;
+
+    fn not(self) -> Self::Output {
+        Self::new(InnerFieldRuleSelector::Not(Box::new(self)))
+    }
+}
+
+impl Display for FieldRuleSelector {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        fn join(selectors: &[FieldRuleSelector]) -> String {
+            selectors.iter()
+                .map(|selector| selector.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        }
+        match &self.inner {
+            InnerFieldRuleSelector::All => {
+                write!(f, "all")
+            }
+            InnerFieldRuleSelector::ItemIdent(value) => {
+                write!(f, "item({})", value)
+            }
+            InnerFieldRuleSelector::ItemAttribute(value) => {
+                write!(f, "item_attribute({})", value)
+            }
+            InnerFieldRuleSelector::FieldIdent(value) => {
+                write!(f, "field({})", value)
+            }
+            InnerFieldRuleSelector::FieldType(value) => {
+                write!(f, "field_type({})", value)
+            }
+            InnerFieldRuleSelector::FieldAttribute(value) => {
+                write!(f, "field_attribute({})", value)
+            }
+            InnerFieldRuleSelector::AllOf(selectors) => {
+                write!(f, "all_of({})", join(selectors))
+            }
+            InnerFieldRuleSelector::AnyOf(selectors) => {
+                write!(f, "any_of({})", join(selectors))
+            }
+            InnerFieldRuleSelector::Not(selector) => {
+                write!(f, "not({})", selector)
+            }
+        }
+    }
+}
\ No newline at end of file
@@ -1,11 +1,23 @@
+use crate::field_rule_selector::FieldRuleSelector;
+use crate::field_type_pattern::FieldTypePattern;
+use crate::generate_error::{Collision, CollisionKind, GenerateError};
+use crate::naming::Naming;
+use crate::rule_error::RuleError;
+use crate::rule_file::RuleFormat;
 use crate::generator::Generator;
-use crate::test_utils::asserts::{assert_builder_with_rules, assert_method};
-use crate::test_utils::fixtures::generator;
+use crate::imports::imported_paths;
+use crate::test_utils::asserts::{assert_builder_with_rules, assert_matched_fields, assert_method};
+use crate::test_utils::fixtures::{generator, modules_with_same_item_names, struct_with_item_attributes, struct_with_mixed_fields, struct_with_private_visibility, struct_with_shared_complex_fields};
+use ast_shaper::items::item::ItemTrait;
 use ast_shaper::items::module_item::ModuleItem;
-use ast_shaper::test_utils::fixtures::{struct_with_field_attributes, struct_with_optional_and_optional_field, struct_with_required_field, struct_with_vec_of_primitive_field};
+use ast_shaper::test_utils::fixtures::{struct_with_boxed_field, struct_with_complex_field, struct_with_field_attributes, struct_with_map_of_primitive_field, struct_with_optional_and_optional_field, struct_with_optional_field, struct_with_ref_counter_and_refcell_field, struct_with_required_field, struct_with_vec_of_primitive_field};
 use ast_shaper::utils::path::Path;
-use quote::quote;
+use quote::__private::TokenStream;
+use quote::{quote, ToTokens};
 use rstest::rstest;
+use std::cell::RefCell;
+use std::rc::Rc;
+use syn::parse_quote;
 
 #[rstest]
 fn with_incomplete_rule(
@@ -239,6 +251,295 @@ fn field_type_selector(
     }
 }
 
+#[rstest]
+#[case("u32", vec!["field_u32"])]
+#[case("Vec<_>", vec!["field_vec", "field_vec_of_string"])]
+#[case("Vec<String>", vec!["field_vec_of_string"])]
+#[case("Option<*>", vec!["field_option"])]
+#[case("HashMap<String, _>", vec!["field_map"])]
+#[case("HashMap<_, String>", vec![])]
+fn field_type_pattern_selector(
+    mut generator: Generator,
+    struct_with_mixed_fields: syn::Item,
+    #[case] pattern: &str,
+    #[case] expected_fields: Vec<&str>
+) {
+    generator.with_rule()
+        .for_all()
+        .with_field_type(pattern)
+        .then_rename("matched_field");
+    assert_matched_fields(&generator, &struct_with_mixed_fields, expected_fields);
+}
+
+#[rstest]
+#[case("Option<*>", "Option<_>")]
+#[case("HashMap<*, *>", "HashMap<_, _>")]
+#[case("*const u32", "* const u32")]
+#[case("Option<*mut u32>", "Option<* mut u32>")]
+fn field_type_pattern_wildcards(
+    #[case] pattern: &str,
+    #[case] expected_pattern: &str
+) {
+    assert_eq!(
+        expected_pattern,
+        FieldTypePattern::parse(pattern).unwrap().to_string()
+    );
+}
+
+#[rstest]
+#[case(|generator: &mut Generator| {
+    generator.with_rule()
+        .for_items_matching("StructWith*Fields")
+        .with_field_ident_matching("field_vec*")
+        .then_rename("matched_field");
+}, vec!["field_vec", "field_vec_of_string"])]
+#[case(|generator: &mut Generator| {
+    generator.with_rule()
+        .for_items_matching("*Request")
+        .with_field_ident_matching("field_vec*")
+        .then_rename("matched_field");
+}, vec![])]
+#[case(|generator: &mut Generator| {
+    generator.with_rule()
+        .for_items_matching_regex("^StructWith.*$")
+        .with_field_ident_matching_regex(r"^field_(u32|map)$")
+        .then_rename("matched_field");
+}, vec!["field_u32", "field_map"])]
+#[case(|generator: &mut Generator| {
+    generator.with_rule()
+        .for_all()
+        .with_field_ident_matching_regex(r"^.*_id$")
+        .then_rename("matched_field");
+}, vec!["owner_id"])]
+fn ident_pattern_selector(
+    mut generator: Generator,
+    struct_with_mixed_fields: syn::Item,
+    #[case] rule: fn(&mut Generator),
+    #[case] expected_fields: Vec<&str>
+) {
+    rule(&mut generator);
+    assert_matched_fields(&generator, &struct_with_mixed_fields, expected_fields);
+}
+
+#[rstest]
+#[case(r"^.*_id$", vec!["user_id"])]
+#[case("*_id", vec!["user_id"])]
+#[case(r"^(user|owner)_\w+$", vec!["user_id", "owner_name"])]
+fn matching_with_regex_syntax(
+    mut generator: Generator,
+    #[case] pattern: &str,
+    #[case] expected_fields: Vec<&str>
+) {
+    let item: syn::Item = parse_quote!(pub struct StructWithIds { user_id: u32, identity: u32, owner_name: String });
+    generator.with_rule()
+        .for_all()
+        .with_field_ident_matching(pattern)
+        .then_rename("matched_field");
+    assert_matched_fields(&generator, &item, expected_fields);
+    assert!(generator.with_rule().try_for_items_matching(pattern).is_ok());
+}
+
+#[rstest]
+fn matching_renamed_field_ident(
+    mut generator: Generator,
+    struct_with_mixed_fields: syn::Item
+) {
+    generator.with_rule()
+        .for_all()
+        .with_field_ident("field_option")
+        .then_rename("field_renamed");
+    generator.with_rule()
+        .for_all()
+        .with_field_ident("field_option")
+        .then_rename("matched_field");
+    assert_matched_fields(&generator, &struct_with_mixed_fields, vec!["field_option"]);
+}
+
+#[rstest]
+fn matching_raw_field_ident(
+    mut generator: Generator
+) {
+    let item: syn::Item = parse_quote!(pub struct StructWithKeywordField { r#type: u32, kind: u32 });
+    generator.with_rule()
+        .for_all()
+        .with_field_ident("type")
+        .then_rename("matched_field");
+    assert_matched_fields(&generator, &item, vec!["r#type"]);
+}
+
+#[rstest]
+#[case("Vec<")]
+#[case("Option<u32")]
+#[case("&")]
+fn invalid_field_type_pattern(
+    mut generator: Generator,
+    #[case] pattern: &str
+) {
+    assert_eq!(
+        Some(RuleError::InvalidPattern {
+            pattern: pattern.to_string(),
+            message: "invalid field type".to_string(),
+        }),
+        generator.with_rule().for_all().try_with_field_type(pattern).err()
+    );
+    assert!(FieldRuleSelector::try_field_type(pattern).is_err());
+}
+
+#[rstest]
+#[case(|generator: &mut Generator| {
+    generator.with_rule()
+        .for_all()
+        .with_field_attribute("serde(skip)")
+        .then_rename("matched_field");
+}, vec!["field_skipped"])]
+#[case(|generator: &mut Generator| {
+    generator.with_rule()
+        .for_all()
+        .with_field_attribute("deprecated")
+        .then_rename("matched_field");
+}, vec!["field_deprecated"])]
+#[case(|generator: &mut Generator| {
+    generator.with_rule()
+        .for_items_with_attribute("derive(Serialize)")
+        .and_all_fields()
+        .then_discard_attribute("deprecated");
+    generator.with_rule()
+        .for_items_with_attribute("derive(Serialize)")
+        .with_field_attribute("serde(skip)")
+        .then_rename("matched_field");
+}, vec!["field_skipped"])]
+#[case(|generator: &mut Generator| {
+    generator.with_rule()
+        .for_items_with_attribute("derive(Deserialize)")
+        .with_field_attribute("serde(skip)")
+        .then_rename("matched_field");
+}, vec![])]
+#[case(|generator: &mut Generator| {
+    generator.with_rule()
+        .for_items_with_attribute("derive(Serialize, Debug)")
+        .with_field_attribute("serde(skip)")
+        .then_rename("matched_field");
+}, vec!["field_skipped"])]
+#[case(|generator: &mut Generator| {
+    generator.with_rule()
+        .for_items_with_attribute("derive(Serialize, Deserialize)")
+        .with_field_attribute("serde(skip)")
+        .then_rename("matched_field");
+}, vec![])]
+#[case(|generator: &mut Generator| {
+    generator.with_rule()
+        .for_items_with_attribute("derive")
+        .with_field_attribute("serde")
+        .then_rename("matched_field");
+}, vec!["field_skipped"])]
+fn attribute_selector(
+    mut generator: Generator,
+    struct_with_item_attributes: syn::Item,
+    #[case] rule: fn(&mut Generator),
+    #[case] expected_fields: Vec<&str>
+) {
+    rule(&mut generator);
+    assert_matched_fields(&generator, &struct_with_item_attributes, expected_fields);
+}
+
+#[rstest]
+#[case(|generator: &mut Generator| {
+    generator.with_rule()
+        .for_all()
+        .with_fields(FieldRuleSelector::any_of(vec![
+            FieldRuleSelector::field_ident("field_u32"),
+            FieldRuleSelector::field_type("Vec<_>")
+        ]))
+        .then_rename("matched_field");
+}, vec!["field_u32", "field_vec", "field_vec_of_string"])]
+#[case(|generator: &mut Generator| {
+    generator.with_rule()
+        .for_all()
+        .with_fields(FieldRuleSelector::all_of(vec![
+            FieldRuleSelector::field_type("Vec<_>"),
+            !FieldRuleSelector::field_ident("field_vec")
+        ]))
+        .then_rename("matched_field");
+}, vec!["field_vec_of_string"])]
+#[case(|generator: &mut Generator| {
+    generator.with_rule()
+        .for_items(FieldRuleSelector::any_of(vec![
+            FieldRuleSelector::item("ItemThatDoesNotExist"),
+            FieldRuleSelector::item("StructWithMixedFields")
+        ]))
+        .with_field_ident("field_option")
+        .then_rename("matched_field");
+}, vec!["field_option"])]
+#[case(|generator: &mut Generator| {
+    generator.with_rule()
+        .for_items(!FieldRuleSelector::item("StructWithMixedFields"))
+        .with_field_ident("field_option")
+        .then_rename("matched_field");
+}, vec![])]
+#[case(|generator: &mut Generator| {
+    generator.with_rule()
+        .for_all()
+        .with_field_ident_matching("field_*")
+        .except_field("field_map")
+        .except(FieldRuleSelector::field_type("Vec<_>"))
+        .then_rename("matched_field");
+}, vec!["field_u32", "field_option"])]
+fn combined_selector(
+    mut generator: Generator,
+    struct_with_mixed_fields: syn::Item,
+    #[case] rule: fn(&mut Generator),
+    #[case] expected_fields: Vec<&str>
+) {
+    rule(&mut generator);
+    assert_matched_fields(&generator, &struct_with_mixed_fields, expected_fields);
+}
+
+#[rstest]
+fn rename_all_except(
+    mut generator: Generator,
+    struct_with_shared_complex_fields: syn::Item
+) {
+    generator.with_rule()
+        .for_all()
+        .and_all_fields()
+        .except_field("other_field")
+        .then_rename("matched_field");
+    assert_matched_fields(&generator, &struct_with_shared_complex_fields, vec!["field"]);
+}
+
+#[rstest]
+fn discard_attributes_by_path(
+    mut generator: Generator
+) {
+    let item: syn::Item = parse_quote! {
+        pub struct StructWithAttributes {
+            #[deprecated(note = "use field")]
+            field_old: u32,
+            #[serde(rename = "x")]
+            #[doc = "Documented."]
+            field_renamed: u32,
+            #[attribute_as_name_value = value]
+            field: u32,
+        }
+    };
+    generator.with_rule()
+        .for_all()
+        .and_all_fields()
+        .then_discard_attribute("deprecated")
+        .then_discard_attribute("serde")
+        .then_discard_attribute("attribute_as_name_value");
+    let attributes = generator.generate_fields(&item).iter()
+        .map(|field| {
+            let attributes = &field.item.attrs;
+            quote!(#(#attributes)*).to_string()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![String::new(), quote!(#[doc = "Documented."]).to_string(), String::new()],
+        attributes
+    );
+}
+
 #[rstest]
 fn discard_attributes(
     mut generator: Generator,
@@ -279,12 +580,40 @@ fn discard_attributes(
     }
 }
 
+#[rstest]
+fn discard_attribute_entry(
+    mut generator: Generator,
+    struct_with_field_attributes: ModuleItem
+) {
+    let (_, _, item) = assert_builder_with_rules(
+        &struct_with_field_attributes,
+        &mut generator,
+        Path::new("Option").with(Path::new("u32")).to_owned(),
+        vec![
+            |generator: &mut Generator| {
+                generator.with_rule()
+                    .for_all()
+                    .with_field_ident("field_with_attribute_as_list")
+                    .then_discard_attribute("attribute_as_list(value2)");
+            }
+        ]
+    );
+    let field = item.item.fields.iter()
+        .find(|field| field.ident.as_ref().unwrap() == "field_with_attribute_as_list")
+        .unwrap();
+    let attributes = &field.attrs;
+    assert_eq!(
+        quote!(#[attribute_as_list(value1)]).to_string(),
+        quote!(#(#attributes)*).to_string()
+    );
+}
+
 #[rstest]
 fn rename(
     mut generator: Generator,
     struct_with_required_field: ModuleItem
 ) {
-    let (generator, _, item) = assert_builder_with_rules(
+    let (generator, item_ident, item) = assert_builder_with_rules(
         &struct_with_required_field,
         &mut generator,
         Path::new("Option").with(Path::new("u32")).to_owned(),
@@ -301,6 +630,31 @@ fn rename(
     let struct_item = item.item;
     let field = struct_item.fields.iter().last().unwrap();
     assert_eq!("renamed_field", field.ident.as_ref().unwrap().to_string());
+    let functions = &item.impl_items.first().unwrap().functions;
+    assert_method(
+        &functions,
+        quote! {
+            pub fn with_renamed_field(&mut self, value: u32) -> &mut Self {
+                self.renamed_field = Some(value);
+                self
+            }
+        }
+    );
+    assert_method(
+        &functions,
+        quote! {
+            pub fn build(&self) -> #item_ident {
+                #item_ident {
+                    field: if let Some(value) = self.renamed_field.clone() {
+                        value
+                    }
+                    else {
+                        panic!("field 'renamed_field' is required");
+                    }
+                }
+            }
+        }
+    );
 }
 
 #[rstest]
@@ -544,4 +898,1029 @@ fn map_from_optional_and_optional_to_vec(
             }
         }
     );
+}
+
+#[rstest]
+fn map_from_vec_to_set(
+    mut generator: Generator,
+    struct_with_vec_of_primitive_field: ModuleItem
+) {
+    let (generator, item_ident, item) = assert_builder_with_rules(
+        &struct_with_vec_of_primitive_field,
+        &mut generator,
+        Path::new("Option")
+            .with(Path::new("HashSet").with(Path::new("u64")).to_owned())
+            .to_owned(),
+        vec![
+            |generator: &mut Generator| {
+                generator.with_rule()
+                    .for_all()
+                    .with_field_ident("field")
+                    .then_map(Path::new("HashSet").with(Path::new("u64")).to_owned());
+            }
+        ]
+    );
+    assert_eq!(1, generator.field_rules.borrow().len());
+    let functions = &item.impl_items.first().unwrap().functions;
+    assert_method(
+        &functions,
+        quote! {
+            pub fn with_field(&mut self, value: u64) -> &mut Self {
+                if let None = self.field {
+                    self.field = Some(HashSet::new());
+                }
+                self.field.as_mut().unwrap().insert(value);
+                self
+            }
+        }
+    );
+    assert_method(
+        &functions,
+        quote! {
+            pub fn set_field(&mut self, values: HashSet<u64>) -> &mut Self {
+                self.field = Some(values);
+                self
+            }
+        }
+    );
+    assert_method(
+        &functions,
+        quote! {
+            pub fn build(&self) -> #item_ident {
+                #item_ident {
+                    field: if let Some(value) = self.field.clone() {
+                        value.into_iter()
+                            .map(|value| value.into())
+                            .collect()
+                    }
+                    else {
+                        panic!("field 'field' is required");
+                    }
+                }
+            }
+        }
+    );
+}
+
+#[rstest]
+fn map_from_map_to_map(
+    mut generator: Generator,
+    struct_with_map_of_primitive_field: ModuleItem
+) {
+    let (generator, item_ident, item) = assert_builder_with_rules(
+        &struct_with_map_of_primitive_field,
+        &mut generator,
+        Path::new("Option")
+            .with(Path::new("HashMap")
+                .with(Path::new("u64"))
+                .with(Path::new("u64"))
+                .to_owned()
+            )
+            .to_owned(),
+        vec![
+            |generator: &mut Generator| {
+                generator.with_rule()
+                    .for_all()
+                    .with_field_ident("field")
+                    .then_map(Path::new("HashMap")
+                        .with(Path::new("u64"))
+                        .with(Path::new("u64"))
+                        .to_owned()
+                    );
+            }
+        ]
+    );
+    assert_eq!(1, generator.field_rules.borrow().len());
+    let functions = &item.impl_items.first().unwrap().functions;
+    assert_method(
+        &functions,
+        quote! {
+            pub fn with_field(&mut self, key: u64, value: u64) -> &mut Self {
+                if let None = self.field {
+                    self.field = Some(HashMap::new());
+                }
+                self.field.as_mut().unwrap().insert(key, value);
+                self
+            }
+        }
+    );
+    assert_method(
+        &functions,
+        quote! {
+            pub fn build(&self) -> #item_ident {
+                #item_ident {
+                    field: if let Some(value) = self.field.clone() {
+                        value.into_iter()
+                            .map(|(key, value)| (key.into(), value.into()))
+                            .collect()
+                    }
+                    else {
+                        panic!("field 'field' is required");
+                    }
+                }
+            }
+        }
+    );
+}
+
+#[rstest]
+fn map_from_optional_to_optional(
+    mut generator: Generator,
+    struct_with_optional_field: ModuleItem
+) {
+    let (generator, item_ident, item) = assert_builder_with_rules(
+        &struct_with_optional_field,
+        &mut generator,
+        Path::new("Option").with(Path::new("u64")).to_owned(),
+        vec![
+            |generator: &mut Generator| {
+                generator.with_rule()
+                    .for_all()
+                    .with_field_ident("field")
+                    .then_map(Path::new("Option").with(Path::new("u64")).to_owned());
+            }
+        ]
+    );
+    assert_eq!(1, generator.field_rules.borrow().len());
+    let functions = &item.impl_items.first().unwrap().functions;
+    assert_method(
+        &functions,
+        quote! {
+            pub fn with_field(&mut self, value: u64) -> &mut Self {
+                self.field = Some(value);
+                self
+            }
+        }
+    );
+    assert_method(
+        &functions,
+        quote! {
+            pub fn build(&self) -> #item_ident {
+                #item_ident {
+                    field: if let Some(value) = self.field.clone() {
+                        Some(value.into())
+                    }
+                    else {
+                        None
+                    }
+                }
+            }
+        }
+    );
+}
+
+#[rstest]
+fn map_from_complex_to_primitive(
+    mut generator: Generator,
+    struct_with_complex_field: ModuleItem
+) {
+    let (generator, item_ident, item) = assert_builder_with_rules(
+        &struct_with_complex_field,
+        &mut generator,
+        Path::new("Option").with(Path::new("String")).to_owned(),
+        vec![
+            |generator: &mut Generator| {
+                generator.with_rule()
+                    .for_all()
+                    .with_field_ident("field")
+                    .then_map(Path::new("String"));
+            }
+        ]
+    );
+    assert_eq!(1, generator.field_rules.borrow().len());
+    let functions = &item.impl_items.first().unwrap().functions;
+    assert_method(
+        &functions,
+        quote! {
+            pub fn with_field(&mut self, value: String) -> &mut Self {
+                self.field = Some(value);
+                self
+            }
+        }
+    );
+    assert_method(
+        &functions,
+        quote! {
+            pub fn build(&self) -> #item_ident {
+                #item_ident {
+                    field: if let Some(value) = self.field.clone() {
+                        value.into()
+                    }
+                    else {
+                        panic!("field 'field' is required");
+                    }
+                }
+            }
+        }
+    );
+}
+
+#[rstest]
+#[case(struct_with_boxed_field(), quote!(Box::new(value.into())))]
+#[case(struct_with_ref_counter_and_refcell_field(), quote!(Rc::new(RefCell::new(value.into()))))]
+fn map_from_generic_to_primitive(
+    mut generator: Generator,
+    #[case] module: ModuleItem,
+    #[case] expected_statement: TokenStream
+) {
+    let (_, item_ident, item) = assert_builder_with_rules(
+        &module,
+        &mut generator,
+        Path::new("Option").with(Path::new("u64")).to_owned(),
+        vec![
+            |generator: &mut Generator| {
+                generator.with_rule()
+                    .for_all()
+                    .with_field_ident("field")
+                    .then_map(Path::new("u64"));
+            }
+        ]
+    );
+    let functions = &item.impl_items.first().unwrap().functions;
+    assert_method(
+        &functions,
+        quote! {
+            pub fn build(&self) -> #item_ident {
+                #item_ident {
+                    field: if let Some(value) = self.field.clone() {
+                        #expected_statement
+                    }
+                    else {
+                        panic!("field 'field' is required");
+                    }
+                }
+            }
+        }
+    );
+}
+
+#[rstest]
+#[case(struct_with_vec_of_primitive_field(), |generator: &mut Generator| {
+    generator.with_rule()
+        .for_all()
+        .with_field_ident("field")
+        .then_map(Path::new("Option").with(Path::new("u64")).to_owned());
+})]
+#[case(struct_with_map_of_primitive_field(), |generator: &mut Generator| {
+    generator.with_rule()
+        .for_all()
+        .with_field_ident("field")
+        .then_map_to_vec(Path::new("u64"));
+})]
+#[case(struct_with_required_field(), |generator: &mut Generator| {
+    generator.with_rule()
+        .for_all()
+        .with_field_ident("field")
+        .then_map(Path::new("HashMap").with(Path::new("u32")).with(Path::new("u32")).to_owned());
+})]
+fn map_unsupported(
+    mut generator: Generator,
+    #[case] module: ModuleItem,
+    #[case] rule: fn(&mut Generator)
+) {
+    rule(&mut generator);
+    let item = module.items.first().unwrap();
+    assert!(matches!(
+        generator.try_generate(&item.to_syn_item()),
+        Err(GenerateError::UnsupportedRemap { item: value, field, .. }) if value == item.ident() && field == "field"
+    ));
+}
+
+#[rstest]
+#[case(Path::new("Box").with(Path::new("u64")).to_owned())]
+#[case(Path::new("Rc").with(Path::new("RefCell").with(Path::new("u64")).to_owned()).to_owned())]
+#[case(Path::new("HashMap").with(Path::new("u64")).to_owned())]
+#[case(Path::new("Option"))]
+#[case(Path::new("Vec").with(Path::new("HashMap").with(Path::new("u64")).to_owned()).to_owned())]
+fn try_map_to_invalid_type(
+    mut generator: Generator,
+    #[case] ty: Path
+) {
+    assert!(matches!(
+        generator.with_rule()
+            .for_all()
+            .with_field_ident("field")
+            .try_then_map(ty),
+        Err(RuleError::InvalidType { .. })
+    ));
+}
+
+#[rstest]
+fn try_map_unsupported(
+    mut generator: Generator,
+    struct_with_vec_of_primitive_field: ModuleItem
+) {
+    generator.with_rule()
+        .for_all()
+        .with_field_ident("field")
+        .try_then_map(Path::new("Option").with(Path::new("u64")).to_owned())
+        .unwrap();
+    assert!(matches!(
+        generator.try_generate(&struct_with_vec_of_primitive_field.items.first().unwrap().to_syn_item()),
+        Err(GenerateError::UnsupportedRemap { .. })
+    ));
+    assert!(matches!(
+        generator.with_rule()
+            .for_all()
+            .with_field_ident("field")
+            .try_then_map_to_vec(Path::new("Option")),
+        Err(RuleError::InvalidType { .. })
+    ));
+}
+
+#[rstest]
+#[should_panic(expected = "Remapping field 'field' of item 'StructWithVecOfPrimitiveField' from 'Vec<u32>' to 'Option<u64>' is not supported")]
+fn generate_with_unsupported_remap(
+    mut generator: Generator,
+    struct_with_vec_of_primitive_field: ModuleItem
+) {
+    generator.with_rule()
+        .for_all()
+        .with_field_ident("field")
+        .then_map(Path::new("Option").with(Path::new("u64")).to_owned());
+    generator.generate(&struct_with_vec_of_primitive_field.items.first().unwrap().to_syn_item());
+}
+
+#[rstest]
+fn skip(
+    mut generator: Generator,
+    struct_with_required_field: ModuleItem
+) {
+    generator.with_rule()
+        .for_all()
+        .with_field_ident("field")
+        .then_skip("Default::default()");
+    let item = struct_with_required_field.items.first().unwrap();
+    let item_ident = item.ident().parse::<TokenStream>().unwrap();
+    let builders = generator.generate(&item.to_syn_item());
+    let builder = builders.first().unwrap();
+    assert_eq!(0, builder.item.fields.len());
+    let functions = &builder.impl_items.first().unwrap().functions;
+    assert_eq!(2, functions.len());
+    assert_method(
+        &functions,
+        quote! {
+            pub fn new() -> Self {
+                Self {}
+            }
+        }
+    );
+    assert_method(
+        &functions,
+        quote! {
+            pub fn build(&self) -> #item_ident {
+                #item_ident {
+                    field: Default::default()
+                }
+            }
+        }
+    );
+}
+
+#[rstest]
+fn required(
+    mut generator: Generator,
+    struct_with_optional_field: ModuleItem
+) {
+    let (generator, item_ident, item) = assert_builder_with_rules(
+        &struct_with_optional_field,
+        &mut generator,
+        Path::new("Option")
+            .with(Path::new("Option").with(Path::new("u32")).to_owned())
+            .to_owned(),
+        vec![
+            |generator: &mut Generator| {
+                generator.with_rule()
+                    .for_all()
+                    .with_field_ident("field")
+                    .then_required();
+            }
+        ]
+    );
+    assert_eq!(1, generator.field_rules.borrow().len());
+    let functions = &item.impl_items.first().unwrap().functions;
+    assert_method(
+        &functions,
+        quote! {
+            pub fn with_field(&mut self, value: u32) -> &mut Self {
+                self.field = Some(Some(value));
+                self
+            }
+        }
+    );
+    assert_method(
+        &functions,
+        quote! {
+            pub fn with_field_opt(&mut self, value: Option<u32>) -> &mut Self {
+                self.field = Some(value);
+                self
+            }
+        }
+    );
+    assert_method(
+        &functions,
+        quote! {
+            pub fn build(&self) -> #item_ident {
+                #item_ident {
+                    field: if let Some(value) = self.field.clone() {
+                        value
+                    }
+                    else {
+                        panic!("field 'field' is required");
+                    }
+                }
+            }
+        }
+    );
+}
+
+#[rstest]
+fn optional(
+    mut generator: Generator,
+    struct_with_required_field: ModuleItem
+) {
+    let (generator, item_ident, item) = assert_builder_with_rules(
+        &struct_with_required_field,
+        &mut generator,
+        Path::new("Option").with(Path::new("u32")).to_owned(),
+        vec![
+            |generator: &mut Generator| {
+                generator.with_rule()
+                    .for_all()
+                    .with_field_ident("field")
+                    .then_optional();
+            }
+        ]
+    );
+    assert_eq!(1, generator.field_rules.borrow().len());
+    let functions = &item.impl_items.first().unwrap().functions;
+    assert_method(
+        &functions,
+        quote! {
+            pub fn with_field(&mut self, value: u32) -> &mut Self {
+                self.field = Some(value);
+                self
+            }
+        }
+    );
+    assert_method(
+        &functions,
+        quote! {
+            pub fn build(&self) -> #item_ident {
+                #item_ident {
+                    field: if let Some(value) = self.field.clone() {
+                        value
+                    }
+                    else {
+                        Default::default()
+                    }
+                }
+            }
+        }
+    );
+}
+
+#[rstest]
+fn map_with_converter(
+    mut generator: Generator,
+    struct_with_required_field: ModuleItem
+) {
+    let (generator, item_ident, item) = assert_builder_with_rules(
+        &struct_with_required_field,
+        &mut generator,
+        Path::new("Option").with(Path::new("String")).to_owned(),
+        vec![
+            |generator: &mut Generator| {
+                generator.with_rule()
+                    .for_all()
+                    .with_field_ident("field")
+                    .then_map_with(Path::new("String"), "converters::to_field");
+            }
+        ]
+    );
+    assert_eq!(1, generator.field_rules.borrow().len());
+    let functions = &item.impl_items.first().unwrap().functions;
+    assert_method(
+        &functions,
+        quote! {
+            pub fn with_field(&mut self, value: String) -> &mut Self {
+                self.field = Some(value);
+                self
+            }
+        }
+    );
+    assert_method(
+        &functions,
+        quote! {
+            pub fn build(&self) -> #item_ident {
+                #item_ident {
+                    field: if let Some(value) = self.field.clone() {
+                        converters::to_field(value)
+                    }
+                    else {
+                        panic!("field 'field' is required");
+                    }
+                }
+            }
+        }
+    );
+}
+
+#[rstest]
+fn try_map_with_converter(
+    mut generator: Generator,
+    struct_with_required_field: ModuleItem
+) {
+    let (generator, item_ident, item) = assert_builder_with_rules(
+        &struct_with_required_field,
+        &mut generator,
+        Path::new("Option").with(Path::new("String")).to_owned(),
+        vec![
+            |generator: &mut Generator| {
+                generator.with_rule()
+                    .for_all()
+                    .with_field_ident("field")
+                    .then_try_map_with(Path::new("String"), "|value| value.parse()");
+            }
+        ]
+    );
+    assert_eq!(1, generator.field_rules.borrow().len());
+    let functions = &item.impl_items.first().unwrap().functions;
+    assert_method(
+        &functions,
+        quote! {
+            pub fn build(&self) -> #item_ident {
+                #item_ident {
+                    field: if let Some(value) = self.field.clone() {
+                        (|value: String| value.parse())(value).unwrap_or_else(|error| panic!("field 'field' conversion failed: {}", error))
+                    }
+                    else {
+                        panic!("field 'field' is required");
+                    }
+                }
+            }
+        }
+    );
+    assert_method(
+        &functions,
+        quote! {
+            pub fn try_build(&self) -> Result<#item_ident, String> {
+                Ok(#item_ident {
+                    field: if let Some(value) = self.field.clone() {
+                        (|value: String| value.parse())(value).map_err(|error| format!("field 'field' conversion failed: {}", error))?
+                    }
+                    else {
+                        return Err(String::from("field 'field' is required"));
+                    }
+                })
+            }
+        }
+    );
+}
+
+#[rstest]
+fn try_map_nested_with_converter(
+    mut generator: Generator,
+    struct_with_complex_field: ModuleItem
+) {
+    let (_, item_ident, item) = assert_builder_with_rules(
+        &struct_with_complex_field,
+        &mut generator,
+        Path::new("Option").with(Path::new("ComplexTypeBuilder")).to_owned(),
+        vec![
+            |generator: &mut Generator| {
+                generator.with_rule()
+                    .for_item("ComplexType")
+                    .with_field_ident("field")
+                    .then_try_map_with(Path::new("String"), "|value| value.parse()");
+            }
+        ]
+    );
+    let functions = &item.impl_items.first().unwrap().functions;
+    assert_method(
+        &functions,
+        quote! {
+            pub fn try_build(&self) -> Result<#item_ident, String> {
+                Ok(#item_ident {
+                    field: if let Some(value) = self.field.clone() {
+                        value.try_build()?
+                    }
+                    else {
+                        return Err(String::from("field 'field' is required"));
+                    }
+                })
+            }
+        }
+    );
+}
+
+#[rstest]
+fn map_all_with_converter(
+    mut generator: Generator,
+    struct_with_shared_complex_fields: syn::Item
+) {
+    generator.with_rule()
+        .for_all()
+        .and_all_fields()
+        .except_field("other_field")
+        .then_map_with(Path::new("String"), "converters::to_field");
+    let items = generator.generate(&struct_with_shared_complex_fields);
+    let fields = items.first().unwrap().item.fields.iter()
+        .map(|field| field.to_token_stream().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![
+            quote!(field: Option<String>).to_string(),
+            quote!(other_field: Option<ComplexTypeBuilder>).to_string()
+        ],
+        fields
+    );
+}
+
+#[rstest]
+fn rule_report(
+    mut generator: Generator,
+    struct_with_mixed_fields: syn::Item
+) {
+    generator.with_rule()
+        .for_item("StructWithMixedFields")
+        .with_field_type("Vec<_>")
+        .then_rename("matched_field");
+    generator.with_rule()
+        .for_item("StructWithMixedFields")
+        .with_field_ident("field_option")
+        .then_required();
+    generator.with_rule()
+        .for_item("StructWithMixedFeilds")
+        .and_all_fields()
+        .then_discard_attribute("an_attribute");
+    generator.with_rule()
+        .for_all()
+        .with_field_ident("field_that_does_not_exist")
+        .then_map(Path::new("u64"));
+    generator.generate_fields(&struct_with_mixed_fields);
+    generator.generate_fields(&struct_with_mixed_fields);
+    let report = generator.rule_report();
+    assert_eq!(4, report.entries.len());
+    assert_eq!(
+        vec!["discard_attribute(an_attribute)", "map(u64)"],
+        report.unmatched().iter().map(|entry| entry.rule.as_str()).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![
+            "rename(matched_field) when all_of(item(StructWithMixedFields), field_type(Vec<_>))",
+            "  StructWithMixedFields.field_vec",
+            "  StructWithMixedFields.field_vec_of_string",
+            "required when all_of(item(StructWithMixedFields), field(field_option))",
+            "  StructWithMixedFields.field_option",
+            "discard_attribute(an_attribute) when item(StructWithMixedFeilds)",
+            "  (unmatched)",
+            "map(u64) when field(field_that_does_not_exist)",
+            "  (unmatched)",
+        ].join("\n"),
+        report.to_string()
+    );
+}
+
+
+#[rstest]
+#[case("self")]
+#[case("super")]
+#[case("crate")]
+#[case("Self")]
+#[case("r#self")]
+#[case("1field")]
+#[case("field-name")]
+fn rename_to_invalid_ident(
+    mut generator: Generator,
+    #[case] ident: &str
+) {
+    assert!(matches!(
+        generator.with_rule()
+            .for_all()
+            .with_field_ident("field")
+            .try_then_rename(ident),
+        Err(RuleError::InvalidIdent { .. })
+    ));
+    assert_eq!(0, generator.field_rules.borrow().len());
+}
+
+#[rstest]
+fn try_rules_with_invalid_selector(
+    mut generator: Generator
+) {
+    assert_eq!(
+        Some(RuleError::SelectorTargetsAllFields {
+            action: "rename".to_string(),
+            selector: "all".to_string(),
+        }),
+        generator.with_rule()
+            .for_all()
+            .and_all_fields()
+            .try_then_rename("renamed_field")
+            .err()
+    );
+    assert_eq!(
+        Some(RuleError::SelectorTargetsAllFields {
+            action: "remap".to_string(),
+            selector: "all_of(item(StructWithRequiredField), not(item(StructWithOptionalField)))".to_string(),
+        }),
+        generator.with_rule()
+            .for_item("StructWithRequiredField")
+            .and_all_fields()
+            .except(FieldRuleSelector::item("StructWithOptionalField"))
+            .try_then_map(Path::new("u64"))
+            .err()
+    );
+    assert!(generator.with_rule()
+        .for_all()
+        .with_field_ident("field")
+        .try_then_map_with(Path::new("String"), "converters::")
+        .is_err_and(|error| matches!(error, RuleError::InvalidConverter { .. }))
+    );
+    assert!(generator.with_rule()
+        .for_all()
+        .with_field_ident("field")
+        .try_then_skip("1 +")
+        .is_err_and(|error| matches!(error, RuleError::InvalidDefault { .. }))
+    );
+    assert_eq!(0, generator.field_rules.borrow().len());
+}
+
+#[rstest]
+fn try_rules_with_valid_selector(
+    mut generator: Generator
+) {
+    let result = generator.with_rule()
+        .for_all()
+        .with_field_ident("field")
+        .try_then_rename("renamed_field")
+        .and_then(|rule| rule.try_then_map(Path::new("u64")))
+        .and_then(|rule| rule.try_then_required())
+        .map(|_| ());
+    assert_eq!(Ok(()), result);
+    assert_eq!(3, generator.field_rules.borrow().len());
+}
+
+
+#[rstest]
+fn load_rules_from_toml(
+    mut generator: Generator,
+    struct_with_mixed_fields: syn::Item
+) {
+    let content = r#"
+        [[rules]]
+        item = "StructWithMixedFields"
+        field = "field_u32"
+        rename = "renamed_field"
+        map = "u64"
+
+        [[rules]]
+        fields_matching = "field_vec*"
+        skip = true
+
+        [[rules]]
+        field = "field_option"
+        default = "Some(1)"
+    "#;
+    generator.load_rules_from_str(content, RuleFormat::Toml).unwrap();
+    assert_eq!(3, generator.field_rules.borrow().len());
+    let fields = generator.generate_fields(&struct_with_mixed_fields);
+    assert_eq!("renamed_field", fields[0].ident);
+    assert_eq!("u64", fields[0].ty.unwrap().to_string());
+    assert!(fields[1].is_skipped);
+    assert!(fields[2].is_skipped);
+    assert!(!fields[3].is_required);
+    assert!(fields[3].default.is_some());
+    assert!(!fields[4].is_skipped);
+}
+
+#[rstest]
+fn load_rules_from_json(
+    mut generator: Generator,
+    struct_with_mixed_fields: syn::Item
+) {
+    let content = r#"{
+        "rules": [
+            { "items_matching": "StructWith*", "field": "field_map", "discard_attribute": ["serde"] }
+        ]
+    }"#;
+    generator.load_rules_from_str(content, RuleFormat::Json).unwrap();
+    generator.generate_fields(&struct_with_mixed_fields);
+    let report = generator.rule_report();
+    assert!(!report.has_unmatched());
+}
+
+#[rstest]
+#[case(RuleFormat::Toml, "[[rules]]\nfield = \"field\"", RuleError::MissingAction { index: 0 })]
+#[case(RuleFormat::Toml, "[[rules]]\nrename = \"renamed_field\"", RuleError::SelectorTargetsAllFields {
+    action: "rename".to_string(),
+    selector: "all".to_string(),
+})]
+fn load_invalid_rules(
+    mut generator: Generator,
+    #[case] format: RuleFormat,
+    #[case] content: &str,
+    #[case] expected_error: RuleError
+) {
+    assert_eq!(Err(expected_error), generator.load_rules_from_str(content, format));
+    assert_eq!(0, generator.field_rules.borrow().len());
+}
+
+#[rstest]
+fn load_rules_with_invalid_regex(
+    mut generator: Generator
+) {
+    let content = r#"{ "rules": [{ "fields_matching_regex": "(", "skip": true }] }"#;
+    assert!(matches!(
+        generator.load_rules_from_str(content, RuleFormat::Json),
+        Err(RuleError::InvalidPattern { pattern, .. }) if pattern == "("
+    ));
+    assert_eq!(0, generator.field_rules.borrow().len());
+}
+
+#[rstest]
+fn load_rules_with_unsupported_format(
+    mut generator: Generator
+) {
+    assert_eq!(
+        Err(RuleError::UnsupportedFormat { path: "rules.yaml".to_string() }),
+        generator.load_rules("rules.yaml")
+    );
+}
+
+
+#[rstest]
+fn visibility(
+    mut generator: Generator,
+    struct_with_private_visibility: syn::Item
+) {
+    generator.with_rule()
+        .for_all()
+        .with_field_ident("field_internal")
+        .then_visibility("pub(crate)");
+    let items = generator.generate(&struct_with_private_visibility);
+    assert_eq!(syn::Visibility::Inherited, items.first().unwrap().item.vis);
+    let visibilities = generator.generate_fields(&struct_with_private_visibility).iter()
+        .map(|field| {
+            match field.generate_set_method() {
+                syn::ImplItem::Fn(value) => value.vis.to_token_stream().to_string(),
+                _ => panic!("Expected method")
+            }
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(vec!["".to_string(), quote!(pub(crate)).to_string()], visibilities);
+    assert!(matches!(
+        generator.with_rule()
+            .for_all()
+            .with_field_ident("field")
+            .try_then_visibility("pub(invalid)"),
+        Err(RuleError::InvalidVisibility { .. })
+    ));
+}
+
+#[rstest]
+fn colliding_fields(
+    mut generator: Generator,
+    struct_with_mixed_fields: syn::Item
+) {
+    generator.with_rule()
+        .for_all()
+        .with_field_ident("field_u32")
+        .then_rename("field_vec");
+    let collisions = match generator.try_generate(&struct_with_mixed_fields) {
+        Err(GenerateError::Collisions(value)) => value,
+        _ => panic!("Expected collisions")
+    };
+    let expected = vec![
+        Collision {
+            item: "StructWithMixedFields".to_string(),
+            kind: CollisionKind::BuilderField,
+            name: "field_vec".to_string(),
+            sources: vec!["field_u32".to_string(), "field_vec".to_string()],
+        },
+        Collision {
+            item: "StructWithMixedFields".to_string(),
+            kind: CollisionKind::Method,
+            name: "with_field_vec".to_string(),
+            sources: vec!["field_u32".to_string(), "field_vec".to_string()],
+        },
+    ];
+    assert_eq!(expected, collisions);
+}
+
+#[rstest]
+fn colliding_builder_methods(
+    mut generator: Generator,
+    struct_with_mixed_fields: syn::Item
+) {
+    generator.with_naming(Naming::default().setter_prefix(""));
+    generator.with_rule()
+        .for_all()
+        .with_field_ident("field_u32")
+        .then_rename("build");
+    let collisions = match generator.try_generate(&struct_with_mixed_fields) {
+        Err(GenerateError::Collisions(value)) => value,
+        _ => panic!("Expected collisions")
+    };
+    assert_eq!(
+        vec![Collision {
+            item: "StructWithMixedFields".to_string(),
+            kind: CollisionKind::Method,
+            name: "build".to_string(),
+            sources: vec!["builder".to_string(), "field_u32".to_string()],
+        }],
+        collisions
+    );
+}
+
+#[rstest]
+fn colliding_setter_and_replace_methods(
+    mut generator: Generator,
+    struct_with_vec_of_primitive_field: ModuleItem
+) {
+    generator.with_naming(Naming::default().setter_prefix("set_"));
+    let item = struct_with_vec_of_primitive_field.items.first().unwrap();
+    let collisions = match generator.try_generate(&item.to_syn_item()) {
+        Err(GenerateError::Collisions(value)) => value,
+        _ => panic!("Expected collisions")
+    };
+    assert_eq!(
+        vec![Collision {
+            item: item.ident(),
+            kind: CollisionKind::Method,
+            name: "set_field".to_string(),
+            sources: vec!["field".to_string(), "field".to_string()],
+        }],
+        collisions
+    );
+}
+
+#[rstest]
+fn local_nested_builders(
+    modules_with_same_item_names: Vec<ModuleItem>
+) {
+    let generator = Generator::new(Rc::new(RefCell::new(modules_with_same_item_names.clone())));
+    let module = &modules_with_same_item_names[1];
+    let item = module.items.first().unwrap().to_syn_item();
+    let expected = generator.try_generate(&module.items.last().unwrap().to_syn_item()).unwrap()
+        .first()
+        .map(|builder| builder.to_syn_item());
+    let builder = generator.try_generate(&item).unwrap().iter()
+        .find(|builder| builder.ident() == "ConfigBuilder")
+        .map(|builder| builder.to_syn_item());
+    assert_eq!(expected, builder);
+}
+
+#[rstest]
+fn colliding_nested_builders(
+    modules_with_same_item_names: Vec<ModuleItem>
+) {
+    let generator = Generator::new(Rc::new(RefCell::new(modules_with_same_item_names.clone())));
+    let item = modules_with_same_item_names.last().unwrap().items.first().unwrap().to_syn_item();
+    let collisions = match generator.try_generate(&item) {
+        Err(GenerateError::Collisions(value)) => value,
+        _ => panic!("Expected collisions")
+    };
+    let paths = modules_with_same_item_names[..2].iter()
+        .map(|module| format!("{}::Config", module.ident()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![Collision {
+            item: "Service".to_string(),
+            kind: CollisionKind::Builder,
+            name: "ConfigBuilder".to_string(),
+            sources: paths,
+        }],
+        collisions
+    );
+}
+
+#[rstest]
+#[case(parse_quote!(use crate::a::Config;), "Config", vec![(vec!["a"], "Config")])]
+#[case(parse_quote!(use super::a::{Config as Settings};), "Settings", vec![(vec!["a"], "Config")])]
+#[case(parse_quote!(use self::c::*;), "Config", vec![(vec!["b", "c"], "Config")])]
+#[case(parse_quote!(use super::a::Config;), "Settings", vec![])]
+fn resolve_imported_paths(
+    #[case] item: syn::Item,
+    #[case] ident: &str,
+    #[case] expected: Vec<(Vec<&str>, &str)>
+) {
+    let expected = expected.into_iter()
+        .map(|(path, name)| (path.into_iter().map(String::from).collect::<Vec<_>>(), name.to_string()))
+        .collect::<Vec<_>>();
+    assert_eq!(expected, imported_paths(&[item], &["b".to_string()], ident));
+}
+
+#[rstest]
+#[should_panic(expected = "Name collisions in generated builder")]
+fn generate_with_collisions(
+    mut generator: Generator,
+    struct_with_mixed_fields: syn::Item
+) {
+    generator.with_rule()
+        .for_all()
+        .with_field_ident("field_option")
+        .then_rename("field_u32");
+    generator.generate(&struct_with_mixed_fields);
 }
\ No newline at end of file
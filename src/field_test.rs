@@ -1,11 +1,22 @@
+use crate::constants::{KEYWORDS, NON_RAW_KEYWORDS};
+use crate::generate_error::{Collision, CollisionKind, GenerateError};
+use crate::generated_module::{GeneratedModule, SkippedItem};
 use crate::generator::Generator;
-use crate::test_utils::asserts::{assert_builder, assert_method};
-use crate::test_utils::fixtures::generator;
+use crate::naming::Naming;
+use crate::test_utils::asserts::{assert_builder, assert_method, assert_struct_literal_fields};
+use crate::test_utils::fixtures::{generator, modules_with_nested_item, modules_with_same_item_names, struct_with_builder_attributes, struct_with_documented_fields, struct_with_gated_fields, struct_with_mixed_fields, struct_with_shared_complex_fields};
+use ast_shaper::items::item::{Item, ItemTrait};
 use ast_shaper::items::module_item::ModuleItem;
+use ast_shaper::items::struct_item::StructItem;
 use ast_shaper::test_utils::fixtures::{struct_with_boxed_field, struct_with_complex_field, struct_with_map_of_primitive_field, struct_with_optional_and_boxed_field, struct_with_optional_and_optional_field, struct_with_optional_field, struct_with_ref_counter_and_refcell_field, struct_with_required_field, struct_with_vec_of_primitive_field};
 use ast_shaper::utils::path::Path;
-use quote::quote;
+use itertools::Itertools;
+use quote::__private::TokenStream;
+use quote::{format_ident, quote, ToTokens};
 use rstest::rstest;
+use std::cell::RefCell;
+use std::rc::Rc;
+use syn::{parse_quote, Fields, ImplItem};
 
 #[rstest]
 fn as_required(
@@ -84,6 +95,24 @@ fn as_optional(
             }
         }
     );
+    assert_method(
+        &functions,
+        quote! {
+            pub fn with_field_opt(&mut self, value: Option<u32>) -> &mut Self {
+                self.field = value;
+                self
+            }
+        }
+    );
+    assert_method(
+        &functions,
+        quote! {
+            pub fn unset_field(&mut self) -> &mut Self {
+                self.field = None;
+                self
+            }
+        }
+    );
     assert_method(
         &functions,
         quote! {
@@ -109,7 +138,9 @@ fn as_optional_and_optional(
     let (item_ident, item) = assert_builder(
         &struct_with_optional_and_optional_field,
         &generator,
-        Path::new("Option").with(Path::new("u32")).to_owned()
+        Path::new("Option")
+            .with(Path::new("Option").with(Path::new("u32")).to_owned())
+            .to_owned()
     );
     let functions = &item.impl_items.first().unwrap().functions;
     assert_method(
@@ -126,11 +157,29 @@ fn as_optional_and_optional(
         &functions,
         quote! {
             pub fn with_field(&mut self, value: u32) -> &mut Self {
+                self.field = Some(Some(value));
+                self
+            }
+        }
+    );
+    assert_method(
+        &functions,
+        quote! {
+            pub fn with_field_opt(&mut self, value: Option<u32>) -> &mut Self {
                 self.field = Some(value);
                 self
             }
         }
     );
+    assert_method(
+        &functions,
+        quote! {
+            pub fn unset_field(&mut self) -> &mut Self {
+                self.field = None;
+                self
+            }
+        }
+    );
     assert_method(
         &functions,
         quote! {
@@ -333,6 +382,27 @@ fn as_vec_of_primitive(
             }
         }
     );
+    assert_method(
+        &functions,
+        quote! {
+            pub fn with_field_all(&mut self, values: impl IntoIterator<Item = u32>) -> &mut Self {
+                if let None = self.field {
+                    self.field = Some(Vec::new());
+                }
+                self.field.as_mut().unwrap().extend(values);
+                self
+            }
+        }
+    );
+    assert_method(
+        &functions,
+        quote! {
+            pub fn set_field(&mut self, values: Vec<u32>) -> &mut Self {
+                self.field = Some(values);
+                self
+            }
+        }
+    );
     assert_method(
         &functions,
         quote! {
@@ -389,6 +459,27 @@ fn as_map_of_primitive(
             }
         }
     );
+    assert_method(
+        &functions,
+        quote! {
+            pub fn with_field_all(&mut self, values: impl IntoIterator<Item = (u32, u32)>) -> &mut Self {
+                if let None = self.field {
+                    self.field = Some(HashMap::new());
+                }
+                self.field.as_mut().unwrap().extend(values);
+                self
+            }
+        }
+    );
+    assert_method(
+        &functions,
+        quote! {
+            pub fn set_field(&mut self, values: HashMap<u32, u32>) -> &mut Self {
+                self.field = Some(values);
+                self
+            }
+        }
+    );
     assert_method(
         &functions,
         quote! {
@@ -455,4 +546,686 @@ fn as_complex(
             }
         }
     );
+    assert!(!functions.iter().any(|function| function.ident() == "try_build"));
+}
+
+#[rstest]
+fn with_builder_attributes(
+    generator: Generator,
+    struct_with_builder_attributes: syn::Item
+) {
+    let items = generator.generate(&struct_with_builder_attributes);
+    let item = items.first().unwrap();
+    let fields = match &item.item.fields {
+        Fields::Named(value) => value.named.iter().collect::<Vec<_>>(),
+        _ => panic!("Expected named fields")
+    };
+    assert_eq!(2, fields.len());
+    assert_eq!(
+        quote!(#[serde(default)] port: Option<u16>).to_string(),
+        fields[0].to_token_stream().to_string()
+    );
+    assert_eq!(
+        quote!(field_name: Option<String>).to_string(),
+        fields[1].to_token_stream().to_string()
+    );
+    let functions = &item.impl_items.first().unwrap().functions;
+    assert_method(
+        &functions,
+        quote! {
+            pub fn with_port(&mut self, value: u16) -> &mut Self {
+                self.port = Some(value);
+                self
+            }
+        }
+    );
+    assert_method(
+        &functions,
+        quote! {
+            pub fn with_field_name(&mut self, value: impl Into<String>) -> &mut Self {
+                let value = value.into();
+                self.field_name = Some(value);
+                self
+            }
+        }
+    );
+    assert_struct_literal_fields(
+        &functions,
+        "build",
+        vec![
+            quote! {
+                field_port: if let Some(value) = self.port.clone() {
+                    value
+                }
+                else {
+                    8080
+                }
+            },
+            quote!(field_skipped: Default::default()),
+            quote! {
+                field_name: if let Some(value) = self.field_name.clone() {
+                    value
+                }
+                else {
+                    panic!("field 'field_name' is required");
+                }
+            }
+        ]
+    );
+}
+
+#[rstest]
+fn with_custom_naming(
+    mut generator: Generator,
+    struct_with_complex_field: ModuleItem
+) {
+    generator.with_naming(
+        Naming::default()
+            .builder_suffix("Factory")
+            .setter_prefix("")
+            .accessor_prefix("edit_")
+            .new_method("create")
+            .build_method("finish")
+            .try_build_method("try_finish")
+    );
+    let item = struct_with_complex_field.items.first().unwrap();
+    let item_ident = item.ident().parse::<TokenStream>().unwrap();
+    let items = generator.generate(&item.to_syn_item());
+    let item = items.first().unwrap();
+    assert_eq!(format!("{}Factory", item_ident), item.ident());
+    let functions = &item.impl_items.first().unwrap().functions;
+    assert_method(
+        &functions,
+        quote! {
+            pub fn create() -> Self {
+                Self {
+                    field: None
+                }
+            }
+        }
+    );
+    assert_method(
+        &functions,
+        quote! {
+            pub fn edit_field(&mut self) -> &mut ComplexTypeFactory {
+                if let None = self.field {
+                    self.field = Some(ComplexTypeFactory::create());
+                }
+                self.field.as_mut().unwrap()
+            }
+        }
+    );
+    assert_method(
+        &functions,
+        quote! {
+            pub fn finish(&self) -> #item_ident {
+                #item_ident {
+                    field: if let Some(value) = self.field.clone() {
+                        value.finish()
+                    }
+                    else {
+                        panic!("field 'field' is required");
+                    }
+                }
+            }
+        }
+    );
+    let inner_item = items.get(1).unwrap();
+    assert_eq!("ComplexTypeFactory", inner_item.ident());
+}
+
+#[rstest]
+fn with_setter_prefix_of_replace_method(
+    mut generator: Generator,
+    struct_with_vec_of_primitive_field: ModuleItem
+) {
+    let item = struct_with_vec_of_primitive_field.items.first().unwrap().to_syn_item();
+    generator.with_naming(
+        Naming::default()
+            .setter_prefix("set_")
+            .replace_prefix("replace_")
+    );
+    let items = generator.generate(&item);
+    let functions = &items.first().unwrap().impl_items.first().unwrap().functions;
+    assert_method(
+        &functions,
+        quote! {
+            pub fn set_field(&mut self, value: u32) -> &mut Self {
+                if let None = self.field {
+                    self.field = Some(Vec::new());
+                }
+                self.field.as_mut().unwrap().push(value);
+                self
+            }
+        }
+    );
+    assert_method(
+        &functions,
+        quote! {
+            pub fn replace_field(&mut self, values: Vec<u32>) -> &mut Self {
+                self.field = Some(values);
+                self
+            }
+        }
+    );
+}
+
+#[rstest]
+fn with_custom_unset_naming(
+    mut generator: Generator,
+    struct_with_optional_field: ModuleItem
+) {
+    generator.with_naming(
+        Naming::default()
+            .unset_prefix("clear_")
+            .unset_suffix("_value")
+    );
+    let item = struct_with_optional_field.items.first().unwrap().to_syn_item();
+    let items = generator.generate(&item);
+    let functions = &items.first().unwrap().impl_items.first().unwrap().functions;
+    assert_method(
+        &functions,
+        quote! {
+            pub fn clear_field_value(&mut self) -> &mut Self {
+                self.field = None;
+                self
+            }
+        }
+    );
+}
+
+#[rstest]
+fn with_documented_fields(
+    mut generator: Generator,
+    struct_with_documented_fields: syn::Item
+) {
+    fn docs(attributes: &Vec<syn::Attribute>) -> String {
+        quote!(#(#attributes)*).to_string()
+    }
+    generator.with_rule()
+        .for_all()
+        .with_field_ident("field_port")
+        .then_try_map_with(Path::new("String"), "|value| value.parse()");
+    let fields = generator.generate_fields(&struct_with_documented_fields);
+    let setters = fields.iter()
+        .map(|field| {
+            match field.generate_set_method() {
+                ImplItem::Fn(value) => docs(&value.attrs),
+                _ => panic!("Expected method")
+            }
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![
+            quote! {
+                #[doc = r" The port to listen on."]
+                #[doc = ""]
+                #[doc = " This field is required: `build` panics when it is not set."]
+            }.to_string(),
+            quote! {
+                #[doc = " This field is optional and defaults to `None`."]
+            }.to_string()
+        ],
+        setters
+    );
+    let item_impl = generator.generate_struct_impl_item(
+        Default::default(),
+        format_ident!("StructWithDocumentedFields"),
+        parse_quote!(pub),
+        &fields
+    );
+    let methods = item_impl.items.iter()
+        .filter_map(|item| {
+            match item {
+                ImplItem::Fn(value) if value.sig.ident.to_string().ends_with("build") => Some(docs(&value.attrs)),
+                _ => None
+            }
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![
+            quote! {
+                #[doc = " Builds a new `StructWithDocumentedFields`."]
+                #[doc = ""]
+                #[doc = " # Panics"]
+                #[doc = ""]
+                #[doc = " Panics if a required field is not set: `field_port`."]
+                #[doc = " Panics if a field conversion fails: `field_port`."]
+            }.to_string(),
+            quote! {
+                #[doc = " Builds a new `StructWithDocumentedFields`."]
+                #[doc = ""]
+                #[doc = " # Errors"]
+                #[doc = ""]
+                #[doc = " Returns an error if a required field is not set: `field_port`."]
+                #[doc = " Returns an error if a field conversion fails: `field_port`."]
+            }.to_string()
+        ],
+        methods
+    );
+}
+
+#[rstest]
+fn with_documented_defaults(
+    mut generator: Generator
+) {
+    let item: syn::Item = parse_quote! {
+        pub struct StructWithDefaults {
+            #[builder(default = "Uuid::nil()")]
+            field_id: Uuid,
+            field_timeout: Duration,
+        }
+    };
+    generator.with_rule()
+        .for_all()
+        .with_field_ident("field_timeout")
+        .then_default("Duration::from_secs(30)");
+    let setters = generator.generate_fields(&item).iter()
+        .map(|field| {
+            match field.generate_set_method() {
+                ImplItem::Fn(value) => {
+                    let attributes = &value.attrs;
+                    quote!(#(#attributes)*).to_string()
+                }
+                _ => panic!("Expected method")
+            }
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![
+            quote!(#[doc = " This field is optional and defaults to `Uuid::nil()`."]).to_string(),
+            quote!(#[doc = " This field is optional and defaults to `Duration::from_secs(30)`."]).to_string()
+        ],
+        setters
+    );
+}
+
+#[rstest]
+fn with_gated_fields(
+    generator: Generator,
+    struct_with_gated_fields: syn::Item
+) {
+    let items = generator.generate(&struct_with_gated_fields);
+    let fields = match &items.first().unwrap().item.fields {
+        Fields::Named(value) => value.named.iter().collect::<Vec<_>>(),
+        _ => panic!("Expected named fields")
+    };
+    assert_eq!(
+        quote!(#[cfg(feature = "tls")] field_tls: Option<bool>).to_string(),
+        fields[0].to_token_stream().to_string()
+    );
+    assert_eq!(
+        quote!(field_legacy: Option<u32>).to_string(),
+        fields[1].to_token_stream().to_string()
+    );
+    assert_eq!(
+        quote!(#[cfg_attr(feature = "serde", serde(rename = "x"))] field_renamed: Option<u32>).to_string(),
+        fields[2].to_token_stream().to_string()
+    );
+    assert_eq!(
+        quote!(#[cfg_attr(feature = "legacy", serde(skip))] field_old: Option<u32>).to_string(),
+        fields[3].to_token_stream().to_string()
+    );
+    let fields = generator.generate_fields(&struct_with_gated_fields);
+    let setters = fields.iter()
+        .map(|field| {
+            match field.generate_set_method() {
+                ImplItem::Fn(value) => {
+                    value.attrs.iter()
+                        .filter(|attribute| !attribute.path().is_ident("doc"))
+                        .map(|attribute| attribute.to_token_stream().to_string())
+                        .collect::<Vec<_>>()
+                }
+                _ => panic!("Expected method")
+            }
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![
+            vec![quote!(#[cfg(feature = "tls")]).to_string()],
+            vec![quote!(#[deprecated]).to_string()],
+            vec![],
+            vec![quote!(#[cfg_attr(feature = "legacy", deprecated)]).to_string()]
+        ],
+        setters
+    );
+    let item_impl = generator.generate_struct_impl_item(
+        Default::default(),
+        format_ident!("StructWithGatedFields"),
+        parse_quote!(pub),
+        &fields
+    );
+    let item_impl = item_impl.to_token_stream().to_string();
+    assert!(item_impl.contains(&quote!(#[cfg(feature = "tls")] field_tls: None).to_string()));
+    assert!(item_impl.contains(&quote!(#[cfg(feature = "tls")] field_tls: if let Some(value)).to_string()));
+    assert!(!item_impl.contains(&quote!(#[cfg(feature = "tls")] field_legacy).to_string()));
+    assert!(!item_impl.contains(&quote!(serde(rename = "x")).to_string()));
+    assert!(item_impl.contains(&quote!(#[allow(deprecated)] pub fn build).to_string()));
+}
+
+#[rstest]
+fn with_keyword_fields(
+    mut generator: Generator
+) {
+    generator.with_rule()
+        .for_all()
+        .with_field_ident("field")
+        .then_rename("match");
+    let keywords = KEYWORDS.iter()
+        .filter(|keyword| !NON_RAW_KEYWORDS.contains(keyword) && **keyword != "match")
+        .collect::<Vec<_>>();
+    for keyword in keywords {
+        let item: syn::Item = syn::parse_str(
+            format!("pub struct StructWithKeywordField {{ r#{}: u32, field: u32 }}", keyword).as_str()
+        ).unwrap();
+        let items = generator.generate(&item);
+        let fields = match &items.first().unwrap().item.fields {
+            Fields::Named(value) => value.named.iter()
+                .map(|field| field.ident.as_ref().unwrap().to_string())
+                .collect::<Vec<_>>(),
+            _ => panic!("Expected named fields")
+        };
+        assert_eq!(vec![format!("r#{}", keyword), "r#match".to_string()], fields);
+        let setters = generator.generate_fields(&item).iter()
+            .map(|field| {
+                match field.generate_set_method() {
+                    ImplItem::Fn(value) => value.sig.ident.to_string(),
+                    _ => panic!("Expected method")
+                }
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(vec![format!("with_{}", keyword), "with_match".to_string()], setters);
+    }
+    generator.with_naming(Naming::default().setter_prefix(""));
+    let item: syn::Item = parse_quote!(pub struct StructWithKeywordField { r#type: u32 });
+    let setter = match generator.generate_fields(&item).first().unwrap().generate_set_method() {
+        ImplItem::Fn(value) => value.sig.ident.to_string(),
+        _ => panic!("Expected method")
+    };
+    assert_eq!("r#type", setter);
+}
+
+#[rstest]
+#[case(parse_quote!(pub struct StructWithRename { #[builder(rename = "self")] field: u32 }))]
+#[case(parse_quote!(pub struct StructWithRename { #[builder(rename = "field-name")] field: u32 }))]
+#[should_panic(expected = "Invalid builder attribute on field 'field': Invalid identifier")]
+fn with_invalid_builder_rename(
+    generator: Generator,
+    #[case] item: syn::Item
+) {
+    generator.generate(&item);
+}
+
+#[rstest]
+fn with_shared_builders(
+    generator: Generator,
+    struct_with_complex_field: ModuleItem,
+    struct_with_shared_complex_fields: syn::Item
+) {
+    fn idents(items: Vec<StructItem>) -> Vec<String> {
+        items.iter().map(|item| item.ident()).collect()
+    }
+    let item = struct_with_complex_field.items.first().unwrap();
+    assert_eq!(
+        vec!["StructWithSharedComplexFieldsBuilder".to_string(), "ComplexTypeBuilder".to_string()],
+        idents(generator.generate(&struct_with_shared_complex_fields))
+    );
+    assert_eq!(
+        vec![
+            "StructWithSharedComplexFieldsBuilder".to_string(),
+            "ComplexTypeBuilder".to_string(),
+            format!("{}Builder", item.ident())
+        ],
+        idents(generator.generate_batch(&[
+            struct_with_shared_complex_fields.clone(),
+            item.to_syn_item(),
+            item.to_syn_item()
+        ]))
+    );
+}
+
+#[rstest]
+fn with_shared_builder_names(
+    modules_with_same_item_names: Vec<ModuleItem>
+) {
+    let generator = Generator::new(Rc::new(RefCell::new(modules_with_same_item_names.clone())));
+    let module = modules_with_same_item_names.first().unwrap();
+    let item = module.items.first().unwrap().to_syn_item();
+    assert_eq!(1, generator.generate_batch(&[item.clone(), item.clone()]).len());
+    assert_eq!(
+        Err(GenerateError::Collisions(vec![Collision {
+            item: "Config".to_string(),
+            kind: CollisionKind::Builder,
+            name: "ConfigBuilder".to_string(),
+            sources: vec![format!("{}::Config", module.ident()), "Config".to_string()],
+        }])),
+        generator.try_generate_batch(&[item, parse_quote!(pub struct Config { host: String })])
+            .map(|items| items.len())
+    );
+}
+
+#[rstest]
+fn with_all_items(
+    generator: Generator,
+    struct_with_required_field: ModuleItem,
+    struct_with_complex_field: ModuleItem
+) {
+    fn idents(modules: &[GeneratedModule]) -> Vec<String> {
+        modules.iter()
+            .flat_map(|module| module.builders.iter().map(|builder| builder.ident()))
+            .collect()
+    }
+    fn module_idents(modules: &[GeneratedModule], module: &ModuleItem) -> Vec<String> {
+        modules.iter()
+            .filter(|value| value.source.ident() == module.ident())
+            .flat_map(|value| value.builders.iter().map(|builder| builder.ident()))
+            .collect()
+    }
+    let modules = generator.generate_all();
+    assert!(modules.iter().all(|module| module.skipped.is_empty()));
+    let all_idents = idents(&modules);
+    assert_eq!(all_idents.len(), all_idents.iter().unique().count());
+    let required = struct_with_required_field.items.first().unwrap().ident();
+    assert_eq!(
+        vec![format!("{}Builder", required)],
+        module_idents(&modules, &struct_with_required_field)
+    );
+    let item = struct_with_complex_field.items.first().unwrap().ident();
+    assert_eq!(
+        vec![format!("{}Builder", item), "ComplexTypeBuilder".to_string()],
+        module_idents(&modules, &struct_with_complex_field)
+    );
+    let modules = generator.generate_matching(|value| value.ident() == item);
+    assert_eq!(
+        vec![format!("{}Builder", item), "ComplexTypeBuilder".to_string()],
+        idents(&modules)
+    );
+}
+
+#[rstest]
+fn with_nested_items_from_other_modules(
+    modules_with_nested_item: Vec<ModuleItem>
+) {
+    let generator = Generator::new(Rc::new(RefCell::new(modules_with_nested_item)));
+    let modules = generator.generate_all()
+        .iter()
+        .map(|module| {
+            module.builders.iter()
+                .map(|builder| builder.ident())
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![vec!["OuterBuilder".to_string()], vec!["InnerBuilder".to_string()]],
+        modules
+    );
+}
+
+#[rstest]
+fn with_skipped_items(
+    struct_with_required_field: ModuleItem,
+    struct_with_optional_field: ModuleItem
+) {
+    let mut first = struct_with_required_field;
+    first.items = vec![
+        Item::Struct(StructItem::new(parse_quote!(pub struct Config { port: u16 }), vec![])),
+        Item::Struct(StructItem::new(parse_quote!(pub struct Marker;), vec![]))
+    ];
+    let mut second = struct_with_optional_field;
+    second.items = vec![
+        Item::Struct(StructItem::new(parse_quote!(pub struct Config { name: String }), vec![])),
+        Item::Struct(StructItem::new(parse_quote!(pub struct Other { field: u32 }), vec![]))
+    ];
+    let generator = Generator::new(Rc::new(RefCell::new(vec![first.clone(), second.clone()])));
+    let modules = generator.generate_all();
+    let builders = modules.iter()
+        .map(|module| module.builders.iter().map(|builder| builder.ident()).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![vec!["ConfigBuilder".to_string()], vec!["OtherBuilder".to_string()]],
+        builders
+    );
+    let collision = GenerateError::Collisions(vec![Collision {
+        item: "Config".to_string(),
+        kind: CollisionKind::Builder,
+        name: "ConfigBuilder".to_string(),
+        sources: vec![format!("{}::Config", first.ident()), format!("{}::Config", second.ident())],
+    }]);
+    assert_eq!(
+        vec![
+            vec![SkippedItem {
+                item: "Marker".to_string(),
+                reason: "unit structs have no fields".to_string(),
+            }],
+            vec![SkippedItem {
+                item: "Config".to_string(),
+                reason: collision.to_string(),
+            }]
+        ],
+        modules.iter().map(|module| module.skipped.clone()).collect::<Vec<_>>()
+    );
+}
+
+#[rstest]
+#[case(parse_quote!(pub struct UnitStruct;), Some("unit structs have no fields"))]
+#[case(parse_quote!(pub struct TupleStruct(u32);), Some("tuple fields are not supported"))]
+#[case(parse_quote!(pub struct ArrayStruct { field: [u32; 4] }), Some("field 'field' has an unsupported type"))]
+#[case(parse_quote!(pub enum TupleEnum { Variant(u32) }), Some("enums are not supported"))]
+#[case(parse_quote!(pub enum NamedEnum { Variant { field: u32 } }), Some("enums are not supported"))]
+#[case(parse_quote!(pub fn function() {}), Some("only structs are supported"))]
+#[case(parse_quote!(pub struct NamedStruct { field: u32 }), None)]
+fn with_unsupported_items(
+    #[case] item: syn::Item,
+    #[case] expected: Option<&str>
+) {
+    assert_eq!(
+        expected.map(|value| value.to_string()),
+        Generator::unsupported_reason(&item)
+    );
+}
+
+#[rstest]
+#[case("crate::builders", quote!(use super::*;))]
+#[case("crate::model::builders", quote!(use super::*;))]
+fn as_file(
+    generator: Generator,
+    struct_with_mixed_fields: syn::Item,
+    #[case] target_path: &str,
+    #[case] expected_import: TokenStream
+) {
+    let file = generator.generate_file(std::slice::from_ref(&struct_with_mixed_fields), target_path);
+    let attributes = &file.attrs;
+    assert_eq!(
+        quote!(#![doc = " Generated by buildify, do not edit."]).to_string(),
+        quote!(#(#attributes)*).to_string()
+    );
+    let items = file.items.iter()
+        .map(|item| item.to_token_stream().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![
+            expected_import.to_string(),
+            quote!(use std::collections::{HashMap};).to_string(),
+            generator.generate(&struct_with_mixed_fields).first().unwrap().item.to_token_stream().to_string()
+        ],
+        items[..3].to_vec()
+    );
+    assert_eq!(4, items.len());
+}
+
+#[rstest]
+#[case("not a path")]
+#[case("crate")]
+#[case("super::builders")]
+#[case("::crate::builders")]
+fn as_file_with_invalid_target_path(
+    generator: Generator,
+    struct_with_mixed_fields: syn::Item,
+    #[case] target_path: &str
+) {
+    assert!(matches!(
+        generator.try_generate_file(std::slice::from_ref(&struct_with_mixed_fields), target_path),
+        Err(GenerateError::InvalidTargetPath { .. })
+    ));
+}
+
+#[rstest]
+fn as_file_with_source_modules(
+    generator: Generator,
+    struct_with_mixed_fields: syn::Item,
+    struct_with_complex_field: ModuleItem
+) {
+    let item = struct_with_complex_field.items.first().unwrap().to_syn_item();
+    let module = format_ident!("{}", struct_with_complex_field.ident());
+    let file = generator.generate_file(&[struct_with_mixed_fields, item], "crate::builders");
+    let imports = file.items.iter()
+        .filter(|item| matches!(item, syn::Item::Use(_)))
+        .map(|item| item.to_token_stream().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![
+            quote!(use super::*;).to_string(),
+            quote!(use super::#module::*;).to_string(),
+            quote!(use std::collections::{HashMap};).to_string()
+        ],
+        imports
+    );
+    let file = generator.generate_file(
+        &[struct_with_complex_field.items.first().unwrap().to_syn_item()],
+        format!("crate::{}::builders", module).as_str()
+    );
+    assert_eq!(
+        quote!(use super::*;).to_string(),
+        file.items.first().unwrap().to_token_stream().to_string()
+    );
+}
+
+#[rstest]
+fn as_module_files(
+    modules_with_nested_item: Vec<ModuleItem>
+) {
+    let generator = Generator::new(Rc::new(RefCell::new(modules_with_nested_item.clone())));
+    let modules = modules_with_nested_item.iter()
+        .map(|module| format_ident!("{}", module.ident()))
+        .collect::<Vec<_>>();
+    let (outer, inner) = (&modules[0], &modules[1]);
+    let imports = generator.generate_all()
+        .iter()
+        .map(|module| {
+            generator.generate_module_file(module, "crate::builders").items.iter()
+                .filter(|item| matches!(item, syn::Item::Use(_)))
+                .map(|item| item.to_token_stream().to_string())
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![
+            vec![
+                quote!(use super::super::#outer::*;).to_string(),
+                quote!(use super::#inner::*;).to_string()
+            ],
+            vec![quote!(use super::super::#inner::*;).to_string()]
+        ],
+        imports
+    );
 }
@@ -0,0 +1,120 @@
+use quote::ToTokens;
+use std::fmt::{Display, Formatter};
+use syn::{parse_str, GenericArgument, PathArguments, Type};
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum FieldTypePattern {
+    Wildcard,
+    Path {
+        ident: String,
+        arguments: Vec<FieldTypePattern>
+    },
+    Other(String)
+}
+
+impl FieldTypePattern {
+    pub(crate) fn parse(pattern: impl Into<String>) -> Option<Self> {
+        let pattern = Self::replace_wildcards(pattern.into());
+        parse_str::<Type>(pattern.as_str())
+            .ok()
+            .map(|ty| Self::from(&ty))
+    }
+
+    pub(self) fn replace_wildcards(pattern: String) -> String {
+        let characters = pattern.chars().collect::<Vec<_>>();
+        characters.iter()
+            .enumerate()
+            .map(|(index, character)| {
+                if *character != '*' {
+                    return *character;
+                }
+                let previous = characters[..index].iter().rev().find(|value| !value.is_whitespace());
+                let next = characters[index + 1..].iter().find(|value| !value.is_whitespace());
+                let is_standalone = matches!(previous, None | Some('<') | Some(','))
+                    && matches!(next, None | Some('>') | Some(','));
+                match is_standalone {
+                    true => '_',
+                    false => '*'
+                }
+            })
+            .collect()
+    }
+
+    pub(crate) fn matches(&self, ty: &Type) -> bool {
+        self.matches_pattern(&Self::from(ty))
+    }
+
+    pub(self) fn matches_pattern(&self, other: &FieldTypePattern) -> bool {
+        match (self, other) {
+            (FieldTypePattern::Wildcard, _) => true,
+            (
+                FieldTypePattern::Path { ident, arguments },
+                FieldTypePattern::Path { ident: other_ident, arguments: other_arguments }
+            ) => {
+                ident == other_ident
+                    && arguments.len() == other_arguments.len()
+                    && arguments.iter()
+                        .zip(other_arguments.iter())
+                        .all(|(argument, other_argument)| argument.matches_pattern(other_argument))
+            }
+            (FieldTypePattern::Other(value), FieldTypePattern::Other(other_value)) => {
+                value == other_value
+            }
+            _ => false
+        }
+    }
+}
+
+impl From<&Type> for FieldTypePattern {
+    fn from(ty: &Type) -> Self {
+        match ty {
+            Type::Infer(_) => FieldTypePattern::Wildcard,
+            Type::Path(value) if value.qself.is_none() => {
+                let segment = value.path.segments.last().unwrap();
+                let arguments = match &segment.arguments {
+                    PathArguments::AngleBracketed(value) => {
+                        value.args.iter()
+                            .map(|argument| {
+                                match argument {
+                                    GenericArgument::Type(value) => FieldTypePattern::from(value),
+                                    _ => FieldTypePattern::Other(argument.to_token_stream().to_string())
+                                }
+                            })
+                            .collect()
+                    }
+                    _ => vec![]
+                };
+                FieldTypePattern::Path {
+                    ident: segment.ident.to_string(),
+                    arguments,
+                }
+            }
+            _ => FieldTypePattern::Other(ty.to_token_stream().to_string())
+        }
+    }
+}
+
+impl Display for FieldTypePattern {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FieldTypePattern::Wildcard => {
+                write!(f, "_")
+            }
+            FieldTypePattern::Path { ident, arguments } => {
+                match arguments.is_empty() {
+                    true => write!(f, "{}", ident),
+                    false => {
+                        let arguments = arguments.iter()
+                            .map(|argument| argument.to_string())
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        write!(f, "{}<{}>", ident, arguments)
+                    }
+                }
+            }
+            FieldTypePattern::Other(value) => {
+                write!(f, "{}", value)
+            }
+        }
+    }
+}
\ No newline at end of file
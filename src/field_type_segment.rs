@@ -6,11 +6,17 @@ use ast_shaper::utils::path::Path;
 use ast_shaper::utils::{create_generic_type, create_ident};
 use std::fmt::{Debug, Display, Formatter};
 use std::rc::Rc;
-use syn::PathArguments;
+use syn::{Attribute, Generics, PathArguments, Visibility};
 
 #[derive(Debug, Clone)]
 pub(crate) struct ComplexField {
     pub ident: String,
+    pub module: String,
+    pub candidates: Vec<String>,
+    pub builder_ident: String,
+    pub visibility: Visibility,
+    pub attributes: Vec<Attribute>,
+    pub generics: Generics,
     pub inner: Vec<Field>
 }
 
@@ -19,6 +25,12 @@ pub(crate) struct VecField {
     pub item: Rc<FieldTypeSegment>
 }
 
+#[derive(Debug, Clone)]
+pub(crate) struct SetField {
+    pub ty: Path,
+    pub item: Rc<FieldTypeSegment>
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct MapField {
     pub key: Rc<FieldTypeSegment>,
@@ -29,6 +41,7 @@ pub(crate) struct MapField {
 pub(crate) struct OptionField {
     pub ty: Path,
     pub underlying_ty: Rc<FieldTypeSegment>,
+    pub nested: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -37,10 +50,17 @@ pub(crate) struct GenericField {
     pub underlying_ty: Rc<FieldTypeSegment>,
 }
 
+#[derive(Debug, Clone)]
+pub(crate) struct FieldConverter {
+    pub expr: syn::Expr,
+    pub is_fallible: bool,
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct RemappedField {
     pub source: Rc<FieldTypeSegment>,
     pub target: Rc<FieldTypeSegment>,
+    pub converter: Option<FieldConverter>,
 }
 
 #[derive(Debug, Clone)]
@@ -48,6 +68,7 @@ pub(crate) enum InnerFieldTypeSegment {
     Reserved(Path),
     Complex(ComplexField),
     Vec(VecField),
+    Set(SetField),
     Map(MapField),
     Option(OptionField),
     Generic(GenericField),
@@ -70,8 +91,7 @@ impl InnerFieldTypeSegment {
                 value.clone()
             }
             InnerFieldTypeSegment::Complex(value) => {
-                let ty = value.ident.clone();
-                Path::from(Generator::ident(&create_ident(ty)))
+                Path::new(value.builder_ident.clone())
             }
             InnerFieldTypeSegment::Vec(value) => {
                 create_generic_type(
@@ -79,6 +99,12 @@ impl InnerFieldTypeSegment {
                     vec![value.item.inner.unwrap()],
                 )
             }
+            InnerFieldTypeSegment::Set(value) => {
+                create_generic_type(
+                    value.ty.last().unwrap().ident.to_string(),
+                    vec![value.item.inner.unwrap()],
+                )
+            }
             InnerFieldTypeSegment::Map(value) => {
                 create_generic_type(
                     "HashMap",
@@ -113,6 +139,9 @@ impl Display for InnerFieldTypeSegment {
             InnerFieldTypeSegment::Vec(_) => {
                 write!(f, "{}", self.unwrap())
             }
+            InnerFieldTypeSegment::Set(_) => {
+                write!(f, "{}", self.unwrap())
+            }
             InnerFieldTypeSegment::Map(_) => {
                 write!(f, "{}", self.unwrap())
             }
@@ -151,6 +180,14 @@ impl FieldTypeSegment {
                 }),
             }
         }
+        else if ty_ident == "HashSet" || ty_ident == "BTreeSet" {
+            Self {
+                inner: InnerFieldTypeSegment::Set(SetField {
+                    ty,
+                    item: Rc::new(underlying_ty.unwrap().get(0).unwrap().to_owned()),
+                }),
+            }
+        }
         else if ty_ident == "HashMap" {
             let underlying_ty = underlying_ty.unwrap();
             Self {
@@ -167,6 +204,7 @@ impl FieldTypeSegment {
                 inner: InnerFieldTypeSegment::Option(OptionField {
                     ty,
                     underlying_ty: Rc::new(FieldTypeSegment::new(generator, underlying_ty.inner.unwrap_underlying_option().unwrap())),
+                    nested: matches!(underlying_ty.inner, InnerFieldTypeSegment::Option(_)),
                 }),
             }
         }
@@ -175,11 +213,18 @@ impl FieldTypeSegment {
                 inner: InnerFieldTypeSegment::Reserved(ty)
             }
         }
-        else if let Some(item) = generator.find_item(&ty_ident) {
-            let (ident, inner) = Self::wrap(generator, item);
+        else if let Some((module, item, candidates)) = generator.find_item(&ty_ident) {
+            let (ident, visibility, attributes, generics, inner) = Self::wrap(generator, &module, item);
+            let builder_ident = generator.ident(&create_ident(&ident)).to_string();
             Self {
                 inner: InnerFieldTypeSegment::Complex(ComplexField {
                     ident,
+                    module,
+                    candidates,
+                    builder_ident,
+                    visibility,
+                    attributes,
+                    generics,
                     inner
                 }),
             }
@@ -200,30 +245,51 @@ impl FieldTypeSegment {
     }
 
     pub fn map(generator: &Generator, source: FieldTypeSegment, target_ty: Path) -> Self {
+        Self::map_with(generator, source, target_ty, None)
+    }
+
+    pub fn map_with(
+        generator: &Generator,
+        source: FieldTypeSegment,
+        target_ty: Path,
+        converter: Option<FieldConverter>
+    ) -> Self {
         let target = Self::new(generator, target_ty);
         Self {
             inner: InnerFieldTypeSegment::Remap(RemappedField {
                 source: Rc::new(source),
                 target: Rc::new(target),
-
+                converter,
             }),
         }
     }
 
-    pub(self) fn wrap(generator: &Generator, item: Item) -> (String, Vec<Field>) {
-        let (item_ident, item) = match &item {
+    pub(self) fn wrap(generator: &Generator, module: &String, item: Item) -> (String, Visibility, Vec<Attribute>, Generics, Vec<Field>) {
+        let (item_ident, visibility, attributes, generics, item) = match &item {
             Item::Struct(value) => {
                 let item = value.clone();
-                (item.ident(), syn::Item::Struct(item.item.clone()))
+                (
+                    item.ident(),
+                    item.item.vis.clone(),
+                    item.item.attrs.clone(),
+                    item.item.generics.clone(),
+                    syn::Item::Struct(item.item.clone())
+                )
             },
             Item::Enum(value) => {
                 let item = value.clone();
-                (item.ident(), syn::Item::Enum(item.item.clone()))
+                (
+                    item.ident(),
+                    item.item.vis.clone(),
+                    item.item.attrs.clone(),
+                    item.item.generics.clone(),
+                    syn::Item::Enum(item.item.clone())
+                )
             },
             _ => panic!("Expected struct or enum item")
         };
-        let fields = generator.generate_fields(&item);
-        (item_ident, fields)
+        let fields = generator.generate_fields_in(&item, Some(module.clone()));
+        (item_ident, visibility, attributes, generics, fields)
     }
 
     pub(self) fn unwrap_underlying(generator: &Generator, path: &Path) -> (Path, Option<Vec<FieldTypeSegment>>) {
@@ -248,6 +314,27 @@ impl FieldTypeSegment {
         }
     }
 
+    pub(crate) fn is_option(&self) -> bool {
+        match self.inner {
+            InnerFieldTypeSegment::Option(_) => true,
+            _ => false
+        }
+    }
+
+    pub(crate) fn is_nested_option(&self) -> bool {
+        match &self.inner {
+            InnerFieldTypeSegment::Option(value) => value.nested,
+            _ => false
+        }
+    }
+
+    pub(crate) fn target(&self) -> &FieldTypeSegment {
+        match &self.inner {
+            InnerFieldTypeSegment::Remap(value) => &value.target,
+            _ => self
+        }
+    }
+
     pub(crate) fn unwrap(&self) -> Path {
         self.inner.unwrap()
     }
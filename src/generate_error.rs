@@ -0,0 +1,80 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CollisionKind {
+    BuilderField,
+    Method,
+    Builder
+}
+
+impl Display for CollisionKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CollisionKind::BuilderField => write!(f, "builder field"),
+            CollisionKind::Method => write!(f, "method"),
+            CollisionKind::Builder => write!(f, "builder")
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Collision {
+    pub item: String,
+    pub kind: CollisionKind,
+    pub name: String,
+    pub sources: Vec<String>,
+}
+
+impl Display for Collision {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} '{}' of item '{}' is generated from {}",
+            self.kind,
+            self.name,
+            self.item,
+            self.sources.iter()
+                .map(|source| format!("'{}'", source))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GenerateError {
+    Collisions(Vec<Collision>),
+    InvalidTargetPath {
+        path: String,
+        message: String,
+    },
+    UnsupportedRemap {
+        item: String,
+        field: String,
+        from: String,
+        to: String,
+    }
+}
+
+impl Display for GenerateError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GenerateError::Collisions(collisions) => {
+                write!(f, "Name collisions in generated builder:")?;
+                for collision in collisions.iter() {
+                    write!(f, "\n  {}", collision)?;
+                }
+                Ok(())
+            }
+            GenerateError::InvalidTargetPath { path, message } => {
+                write!(f, "Invalid target path '{}': {}", path, message)
+            }
+            GenerateError::UnsupportedRemap { item, field, from, to } => {
+                write!(f, "Remapping field '{}' of item '{}' from '{}' to '{}' is not supported", field, item, from, to)
+            }
+        }
+    }
+}
+
+impl Error for GenerateError {}
\ No newline at end of file
@@ -0,0 +1,32 @@
+use ast_shaper::items::module_item::ModuleItem;
+use ast_shaper::items::struct_item::StructItem;
+use std::fmt::{Display, Formatter};
+use syn::{ItemImpl, ItemStruct};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkippedItem {
+    pub item: String,
+    pub reason: String,
+}
+
+impl Display for SkippedItem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Skipped '{}': {}", self.item, self.reason)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct GeneratedBuilder {
+    pub module: Option<String>,
+    pub dependencies: Vec<String>,
+    pub item: ItemStruct,
+    pub impl_item: ItemImpl,
+}
+
+#[derive(Debug, Clone)]
+pub struct GeneratedModule {
+    pub source: ModuleItem,
+    pub builders: Vec<StructItem>,
+    pub skipped: Vec<SkippedItem>,
+    pub(crate) generated: Vec<GeneratedBuilder>,
+}
\ No newline at end of file
@@ -1,10 +1,20 @@
+use crate::attribute_policy::AttributePolicy;
+use crate::doc::doc_attributes;
 use crate::field::Field;
 use crate::field_rule::{FieldRule, FieldRuleItemSelectorBuilder};
 use crate::field_type_segment::InnerFieldTypeSegment;
+use crate::generate_error::{Collision, CollisionKind, GenerateError};
+use crate::generated_module::{GeneratedBuilder, GeneratedModule, SkippedItem};
+use crate::ident::unraw;
+use crate::imports::{collection_imports, glob_import, imported_paths, module_segments};
+use crate::naming::Naming;
+use crate::rule_error::RuleError;
+use crate::rule_file::{RuleFile, RuleFormat};
+use crate::rule_report::RuleReport;
 use ast_shaper::items::item::{Item, ItemTrait};
 use ast_shaper::items::module_item::ModuleItem;
 use ast_shaper::items::struct_item::StructItem;
-use ast_shaper::utils::create_ident;
+use ast_shaper::utils::{create_generic_type, create_ident};
 use ast_shaper::utils::path::Path;
 use ast_shaper::utils::punctuated::PunctuatedExt;
 use ast_shaper::utils::statement::{Expr, Statement};
@@ -13,12 +23,15 @@ use std::cell::RefCell;
 use std::collections::HashMap;
 use std::rc::Rc;
 use syn::punctuated::Punctuated;
-use syn::{Attribute, Block, Fields, FieldsNamed, FnArg, Generics, Ident, ImplItem, ImplItemFn, ItemImpl, ItemStruct, Receiver, ReturnType, Signature, Token, Type, TypePath, TypeReference, Visibility};
+use syn::{parse_quote, parse_str, Attribute, Block, Fields, FieldsNamed, FnArg, Generics, Ident, ImplItem, ImplItemFn, ItemImpl, ItemStruct, Receiver, ReturnType, Signature, Stmt, Token, Type, TypePath, TypeReference, Visibility};
 
 #[derive(Debug, Clone)]
 pub struct Generator {
     modules: Rc<RefCell<Vec<ModuleItem>>>,
-    pub(crate) field_rules: Rc<RefCell<Vec<FieldRule>>>
+    pub(crate) field_rules: Rc<RefCell<Vec<FieldRule>>>,
+    attribute_policy: Rc<RefCell<AttributePolicy>>,
+    naming: Rc<RefCell<Naming>>,
+    scope: Rc<RefCell<Vec<Option<String>>>>
 }
 
 impl Generator {
@@ -26,33 +39,334 @@ impl Generator {
         Self {
             modules,
             field_rules: Rc::new(RefCell::new(Vec::new())),
+            attribute_policy: Rc::new(RefCell::new(AttributePolicy::default())),
+            naming: Rc::new(RefCell::new(Naming::default())),
+            scope: Rc::new(RefCell::new(Vec::new())),
         }
     }
 
+    pub fn with_naming(&mut self, naming: Naming) -> &mut Self {
+        *self.naming.borrow_mut() = naming;
+        self
+    }
+
+    pub(crate) fn naming(&self) -> Naming {
+        self.naming.borrow().clone()
+    }
+
+    pub fn keep_item_attributes<T: Into<String>>(&mut self, attributes: impl IntoIterator<Item = T>) -> &mut Self {
+        let attributes = attributes.into_iter().map(Into::into).collect();
+        self.attribute_policy.borrow_mut().keep(attributes);
+        self
+    }
+
+    pub fn drop_item_attributes<T: Into<String>>(&mut self, attributes: impl IntoIterator<Item = T>) -> &mut Self {
+        let attributes = attributes.into_iter().map(Into::into).collect();
+        self.attribute_policy.borrow_mut().drop(attributes);
+        self
+    }
+
+    pub fn add_item_attribute(&mut self, attribute: impl Into<String>) -> &mut Self {
+        self.attribute_policy.borrow_mut().add(attribute.into());
+        self
+    }
+
     pub fn with_rule(&mut self) -> FieldRuleItemSelectorBuilder
     {
         FieldRuleItemSelectorBuilder::new(self.field_rules.clone())
     }
     
-    pub(crate) fn ident(ident: &Ident) -> Ident {
-        let item_ident = format!("{}Builder", ident.to_string());
-        create_ident(&item_ident)
+    pub fn load_rules(&mut self, path: impl AsRef<std::path::Path>) -> Result<(), RuleError> {
+        let path = path.as_ref();
+        let format = RuleFormat::from_path(path)?;
+        let content = match std::fs::read_to_string(path) {
+            Ok(value) => value,
+            Err(error) => return Err(RuleError::UnreadableRuleFile {
+                path: path.display().to_string(),
+                message: error.to_string(),
+            })
+        };
+        self.load_rules_from_str(content.as_str(), format)
+    }
+
+    pub fn load_rules_from_str(&mut self, content: &str, format: RuleFormat) -> Result<(), RuleError> {
+        let mut rules = RuleFile::parse(content, format)?.into_rules()?;
+        self.field_rules.borrow_mut().append(&mut rules);
+        Ok(())
+    }
+
+    pub fn rule_report(&self) -> RuleReport {
+        let entries = self.field_rules.borrow().iter()
+            .map(|rule| rule.report())
+            .collect();
+        RuleReport {
+            entries,
+        }
+    }
+
+    pub(crate) fn ident(&self, ident: &Ident) -> Ident {
+        self.naming.borrow().builder_ident(ident)
     }
     
-    pub(crate) fn find_item(&self, ident: &String) -> Option<Item> {
-        self.modules.borrow().iter()
-            .find_map(|module| {
+    pub(crate) fn find_item(&self, ident: &String) -> Option<(String, Item, Vec<String>)> {
+        let modules = self.modules.borrow();
+        let scope = self.scope.borrow().last().cloned().flatten()
+            .and_then(|value| modules.iter().find(|module| module.ident() == value));
+        if let Some(module) = scope {
+            let items = module.items.iter()
+                .map(|item| item.to_syn_item())
+                .collect::<Vec<_>>();
+            let imported = imported_paths(&items, &module_segments(&module.ident()), ident).into_iter()
+                .find_map(|(path, name)| {
+                    let module = modules.iter().find(|module| module_segments(&module.ident()) == path)?;
+                    let item = module.find_item_by(|item| item.ident() == name)?;
+                    Some((module.ident(), item.clone(), Vec::new()))
+                });
+            if imported.is_some() {
+                return imported;
+            }
+            if let Some(item) = module.find_item_by(|item| item.ident() == *ident) {
+                return Some((module.ident(), item.clone(), Vec::new()));
+            }
+        }
+        let candidates = modules.iter()
+            .filter_map(|module| {
                 let item = module.find_item_by(|item| {
                     item.ident() == *ident
                 });
                 match item {
-                    Some(value) => Some(value.clone()),
+                    Some(value) => Some((module.ident(), value.clone())),
                     None => None
                 }
             })
-    } 
+            .collect::<Vec<_>>();
+        let paths = match candidates.len() {
+            0 | 1 => Vec::new(),
+            _ => candidates.iter()
+                .map(|(module, _)| Self::type_path(Some(module), ident))
+                .collect()
+        };
+        candidates.into_iter()
+            .next()
+            .map(|(module, item)| (module, item, paths))
+    }
+
+    pub(crate) fn find_module(&self, item: &syn::Item) -> Option<String> {
+        self.modules.borrow().iter()
+            .find(|module| {
+                module.find_item_by(|value| value.to_syn_item() == *item).is_some()
+            })
+            .map(|module| module.ident())
+    }
+
+    pub(crate) fn type_path(module: Option<&String>, ident: &str) -> String {
+        match module {
+            Some(value) => format!("{}::{}", value, ident),
+            None => ident.to_string()
+        }
+    }
 
     pub fn generate(&self, item: &syn::Item) -> Vec<StructItem> {
+        self.try_generate(item).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_generate(&self, item: &syn::Item) -> Result<Vec<StructItem>, GenerateError> {
+        self.generate_with_registry(item, self.find_module(item), &mut HashMap::new())
+            .map(Self::wrap_builders)
+    }
+
+    pub fn generate_batch(&self, items: &[syn::Item]) -> Vec<StructItem> {
+        self.try_generate_batch(items).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_generate_batch(&self, items: &[syn::Item]) -> Result<Vec<StructItem>, GenerateError> {
+        let mut registry = HashMap::new();
+        let mut builders = Vec::new();
+        for item in items.iter() {
+            let items = self.generate_with_registry(item, self.find_module(item), &mut registry)?;
+            builders.append(&mut Self::wrap_builders(items));
+        }
+        Ok(builders)
+    }
+
+    pub fn generate_file(&self, items: &[syn::Item], target_path: &str) -> syn::File {
+        self.try_generate_file(items, target_path).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_generate_file(&self, items: &[syn::Item], target_path: &str) -> Result<syn::File, GenerateError> {
+        let target = Self::target_segments(target_path)?;
+        let mut registry = HashMap::new();
+        let mut builders = Vec::new();
+        for item in items.iter() {
+            builders.append(&mut self.generate_with_registry(item, self.find_module(item), &mut registry)?);
+        }
+        let imports = builders.iter()
+            .map(|builder| {
+                match &builder.module {
+                    Some(value) => [vec!["crate".to_string()], module_segments(value)].concat(),
+                    None => target[..target.len() - 1].to_vec()
+                }
+            })
+            .unique()
+            .map(|source| glob_import(&target, &source))
+            .collect();
+        Ok(Self::generate_file_item(imports, &builders))
+    }
+
+    pub fn generate_module_file(&self, module: &GeneratedModule, target_path: &str) -> syn::File {
+        self.try_generate_module_file(module, target_path).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_generate_module_file(
+        &self,
+        module: &GeneratedModule,
+        target_path: &str
+    ) -> Result<syn::File, GenerateError> {
+        let root = Self::target_segments(target_path)?;
+        let ident = module.source.ident();
+        let target = [root.clone(), module_segments(&ident)].concat();
+        let mut imports = vec![glob_import(&target, &[vec!["crate".to_string()], module_segments(&ident)].concat())];
+        let mut dependencies = module.generated.iter()
+            .flat_map(|builder| builder.dependencies.iter())
+            .filter(|dependency| **dependency != ident)
+            .unique()
+            .map(|dependency| glob_import(&target, &[root.clone(), module_segments(dependency)].concat()))
+            .collect();
+        imports.append(&mut dependencies);
+        Ok(Self::generate_file_item(imports, &module.generated))
+    }
+
+    fn target_segments(target_path: &str) -> Result<Vec<String>, GenerateError> {
+        let path = match parse_str::<syn::Path>(target_path) {
+            Ok(value) => value,
+            Err(error) => return Err(GenerateError::InvalidTargetPath {
+                path: target_path.to_string(),
+                message: error.to_string(),
+            })
+        };
+        let segments = path.segments.iter()
+            .map(|segment| segment.ident.to_string())
+            .collect::<Vec<_>>();
+        let is_module = path.leading_colon.is_none()
+            && path.segments.iter().all(|segment| segment.arguments.is_none())
+            && segments.len() > 1
+            && segments[0] == "crate";
+        if !is_module {
+            return Err(GenerateError::InvalidTargetPath {
+                path: target_path.to_string(),
+                message: "expected a module path below `crate`".to_string(),
+            });
+        }
+        Ok(segments)
+    }
+
+    fn generate_file_item(imports: Vec<syn::Item>, builders: &[GeneratedBuilder]) -> syn::File {
+        let mut builders = builders.iter()
+            .flat_map(|builder| {
+                vec![syn::Item::Struct(builder.item.clone()), syn::Item::Impl(builder.impl_item.clone())]
+            })
+            .collect::<Vec<_>>();
+        let mut file_items = imports;
+        file_items.extend(collection_imports(&builders));
+        file_items.append(&mut builders);
+        syn::File {
+            shebang: None,
+            attrs: vec![parse_quote!(#![doc = " Generated by buildify, do not edit."])],
+            items: file_items,
+        }
+    }
+
+    pub fn generate_all(&self) -> Vec<GeneratedModule> {
+        self.generate_matching(|_| true)
+    }
+
+    pub fn try_generate_all(&self) -> Result<Vec<GeneratedModule>, GenerateError> {
+        self.try_generate_matching(|_| true)
+    }
+
+    pub fn generate_matching(&self, filter: impl Fn(&Item) -> bool) -> Vec<GeneratedModule> {
+        self.try_generate_matching(filter).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_generate_matching(
+        &self,
+        filter: impl Fn(&Item) -> bool
+    ) -> Result<Vec<GeneratedModule>, GenerateError> {
+        let modules = self.modules.borrow().clone();
+        let mut registry = HashMap::new();
+        let mut generated = modules.iter()
+            .map(|module| GeneratedModule {
+                source: module.clone(),
+                builders: Vec::new(),
+                skipped: Vec::new(),
+                generated: Vec::new(),
+            })
+            .collect::<Vec<_>>();
+        for (index, module) in modules.iter().enumerate() {
+            for item in module.items.iter().filter(|item| filter(item)) {
+                let syn_item = match item {
+                    Item::Struct(_) | Item::Enum(_) => item.to_syn_item(),
+                    _ => continue
+                };
+                match Self::unsupported_reason(&syn_item) {
+                    Some(reason) => generated[index].skipped.push(SkippedItem {
+                        item: item.ident(),
+                        reason,
+                    }),
+                    None => {
+                        let mut item_registry = registry.clone();
+                        let builders = match self.generate_with_registry(&syn_item, Some(module.ident()), &mut item_registry) {
+                            Ok(value) => value,
+                            Err(error) => {
+                                generated[index].skipped.push(SkippedItem {
+                                    item: item.ident(),
+                                    reason: error.to_string(),
+                                });
+                                continue;
+                            }
+                        };
+                        registry = item_registry;
+                        for builder in builders.into_iter() {
+                            let target = builder.module.as_ref()
+                                .and_then(|value| modules.iter().position(|module| module.ident() == *value))
+                                .unwrap_or(index);
+                            generated[target].builders.append(&mut Self::wrap_builders(vec![builder.clone()]));
+                            generated[target].generated.push(builder);
+                        }
+                    }
+                }
+            }
+        }
+        Ok(generated)
+    }
+
+    pub(crate) fn unsupported_reason(item: &syn::Item) -> Option<String> {
+        let fields = match item {
+            syn::Item::Struct(value) => {
+                match &value.fields {
+                    Fields::Unit => return Some("unit structs have no fields".to_string()),
+                    fields => fields.iter().collect::<Vec<_>>()
+                }
+            }
+            syn::Item::Enum(_) => return Some("enums are not supported".to_string()),
+            _ => return Some("only structs are supported".to_string())
+        };
+        fields.iter()
+            .find_map(|field| {
+                match (&field.ident, &field.ty) {
+                    (None, _) => Some("tuple fields are not supported".to_string()),
+                    (Some(_), Type::Path(_)) => None,
+                    (Some(ident), _) => Some(format!("field '{}' has an unsupported type", ident))
+                }
+            })
+    }
+
+    fn generate_with_registry(
+        &self,
+        item: &syn::Item,
+        module: Option<String>,
+        registry: &mut HashMap<String, String>
+    ) -> Result<Vec<GeneratedBuilder>, GenerateError> {
         let attributes = match &item {
             syn::Item::Struct(value) => &value.attrs,
             syn::Item::Enum(value) => &value.attrs,
@@ -68,33 +382,169 @@ impl Generator {
             syn::Item::Enum(value) => &value.ident,
             _ => panic!("Unexpected item type")
         };
-        let fields = self.generate_fields(&item);
-        let struct_item = self.generate_struct_item(
-            attributes.clone(),
-            Visibility::Public(Default::default()),
-            generics.clone(),
-            ident.clone(),
-            &fields
-        );
-        let struct_impl_item = self.generate_struct_impl_item(
-            generics.clone(),
-            ident.clone(),
-            &fields
-        );
-        let mut builders = vec![
-            StructItem::new(struct_item, vec![struct_impl_item])
-        ];
-        let mut inner_builders = self.generate_inner_builders(
-            attributes.clone(),
-            Visibility::Public(Default::default()),
-            generics.clone(),
-            &fields
-        );
+        let visibility = match &item {
+            syn::Item::Struct(value) => &value.vis,
+            syn::Item::Enum(value) => &value.vis,
+            _ => panic!("Unexpected item type")
+        };
+        let attributes = self.attribute_policy.borrow().apply(attributes);
+        let fields = self.generate_fields_in(item, module.clone());
+        if let Some(error) = Self::find_unsupported_remap(&ident.to_string(), &fields) {
+            return Err(error);
+        }
+        let collisions = self.find_collisions(ident, module.as_ref(), &fields);
+        if !collisions.is_empty() {
+            return Err(GenerateError::Collisions(collisions));
+        }
+        let mut builders = Vec::new();
+        let path = Self::type_path(module.as_ref(), &ident.to_string());
+        if Self::register(registry, ident.to_string(), self.ident(ident).to_string(), path)? {
+            builders.push(self.generate_builder(
+                attributes.clone(),
+                visibility.clone(),
+                generics.clone(),
+                ident.clone(),
+                module,
+                &fields
+            ));
+        }
+        let mut inner_builders = self.generate_inner_builders(&fields, registry)?;
         builders.append(&mut inner_builders);
-        builders
+        Ok(builders)
+    }
+
+    fn register(
+        registry: &mut HashMap<String, String>,
+        item: String,
+        builder: String,
+        path: String
+    ) -> Result<bool, GenerateError> {
+        match registry.get(&builder) {
+            Some(value) if *value == path => Ok(false),
+            Some(value) => Err(GenerateError::Collisions(vec![Collision {
+                item,
+                kind: CollisionKind::Builder,
+                name: builder,
+                sources: vec![value.clone(), path],
+            }])),
+            None => {
+                registry.insert(builder, path);
+                Ok(true)
+            }
+        }
     }
 
+    fn find_unsupported_remap(item: &str, fields: &[Field]) -> Option<GenerateError> {
+        fields.iter()
+            .filter(|field| !field.is_skipped)
+            .find_map(|field| {
+                if let Some((from, to)) = field.unsupported_remap() {
+                    return Some(GenerateError::UnsupportedRemap {
+                        item: item.to_string(),
+                        field: field.target_ident.clone(),
+                        from,
+                        to,
+                    });
+                }
+                match &field.ty.target().inner {
+                    InnerFieldTypeSegment::Complex(value) => Self::find_unsupported_remap(&value.ident, &value.inner),
+                    _ => None
+                }
+            })
+    }
+
+    fn find_collisions(&self, ident: &Ident, module: Option<&String>, fields: &Vec<Field>) -> Vec<Collision> {
+        fn group(item: &str, kind: CollisionKind, names: Vec<(String, String)>) -> Vec<Collision> {
+            let mut groups: Vec<(String, Vec<String>)> = Vec::new();
+            for (name, source) in names {
+                match groups.iter_mut().find(|(value, _)| value == &name) {
+                    Some((_, sources)) => sources.push(source),
+                    None => groups.push((name, vec![source]))
+                }
+            }
+            groups.into_iter()
+                .filter(|(_, sources)| sources.len() > 1)
+                .map(|(name, sources)| Collision {
+                    item: item.to_string(),
+                    kind: kind.clone(),
+                    name,
+                    sources,
+                })
+                .collect()
+        }
+        fn collect(
+            generator: &Generator,
+            item: String,
+            path: String,
+            fields: &Vec<Field>,
+            builders: &mut Vec<(String, String)>,
+            collisions: &mut Vec<Collision>
+        ) {
+            if builders.iter().any(|(_, value)| value == &path) {
+                return;
+            }
+            builders.push((generator.ident(&create_ident(&item)).to_string(), path));
+            let is_fallible = Field::has_fallible_fields(fields);
+            let fields = fields.iter()
+                .filter(|field| !field.is_skipped)
+                .collect::<Vec<_>>();
+            let builder_fields = fields.iter()
+                .map(|field| (unraw(&field.ident), field.target_ident.clone()))
+                .collect();
+            collisions.append(&mut group(&item, CollisionKind::BuilderField, builder_fields));
+            let naming = generator.naming();
+            let mut methods = vec![naming.new_method, naming.build_method];
+            if is_fallible {
+                methods.push(naming.try_build_method);
+            }
+            let mut methods = methods.into_iter()
+                .map(|method| (method, "builder".to_string()))
+                .collect::<Vec<_>>();
+            for field in fields.iter() {
+                for method in field.generate_set_methods() {
+                    if let ImplItem::Fn(value) = method {
+                        methods.push((unraw(&value.sig.ident.to_string()), field.target_ident.clone()));
+                    }
+                }
+            }
+            collisions.append(&mut group(&item, CollisionKind::Method, methods));
+            for field in fields.iter() {
+                if let InnerFieldTypeSegment::Complex(value) = &field.ty.target().inner {
+                    if value.candidates.len() > 1 {
+                        collisions.push(Collision {
+                            item: item.clone(),
+                            kind: CollisionKind::Builder,
+                            name: value.builder_ident.clone(),
+                            sources: value.candidates.clone(),
+                        });
+                        continue;
+                    }
+                    let path = Generator::type_path(Some(&value.module), &value.ident);
+                    collect(generator, value.ident.clone(), path, &value.inner, builders, collisions);
+                }
+            }
+        }
+        let mut builders = Vec::new();
+        let mut collisions = Vec::new();
+        let path = Self::type_path(module, &ident.to_string());
+        collect(self, ident.to_string(), path, fields, &mut builders, &mut collisions);
+        collisions.append(&mut group(&ident.to_string(), CollisionKind::Builder, builders));
+        collisions
+    }
+
+    #[cfg(test)]
     pub(crate) fn generate_fields(&self, item: &syn::Item) -> Vec<Field> {
+        self.generate_fields_in(item, self.find_module(item))
+    }
+
+    pub(crate) fn generate_fields_in(&self, item: &syn::Item, module: Option<String>) -> Vec<Field> {
+        self.scope.borrow_mut().push(module);
+        let fields = self.generate_scoped_fields(item);
+        self.scope.borrow_mut().pop();
+        fields
+    }
+
+    fn generate_scoped_fields(&self, item: &syn::Item) -> Vec<Field> {
         let ident = match item {
             syn::Item::Struct(value) => {
                 value.ident.to_string()
@@ -104,6 +554,16 @@ impl Generator {
             }
             _ => panic!("Expected struct or enum item")
         };
+        let attributes = match item {
+            syn::Item::Struct(value) => &value.attrs,
+            syn::Item::Enum(value) => &value.attrs,
+            _ => panic!("Expected struct or enum item")
+        };
+        let visibility = match item {
+            syn::Item::Struct(value) => &value.vis,
+            syn::Item::Enum(value) => &value.vis,
+            _ => panic!("Expected struct or enum item")
+        };
         let mut fields: Vec<&syn::Field> = match &item {
             syn::Item::Struct(value) => {
                 match &value.fields {
@@ -130,9 +590,11 @@ impl Generator {
             .map(|field| {
                 let field = field.clone();
                 let mut field = Field::new(self.clone(), field);
+                field.visibility = visibility.clone();
+                field.apply_builder_attributes();
                 self.field_rules.borrow().iter()
                     .for_each(|rule: &FieldRule| {
-                        rule.apply(&ident,&mut field);
+                        rule.apply(&ident, attributes, &mut field);
                     });
                 field
             })
@@ -151,11 +613,12 @@ impl Generator {
             attrs: attributes,
             vis: visibility,
             struct_token: Default::default(),
-            ident: Self::ident(&ident),
+            ident: self.ident(&ident),
             generics,
             fields: Fields::Named(FieldsNamed {
                 brace_token: Default::default(),
                 named: fields.iter()
+                    .filter(|field| !field.is_skipped)
                     .map(|field| field.unwrap())
                     .collect::<Punctuated<syn::Field, Token![,]>>(),
             }),
@@ -163,17 +626,19 @@ impl Generator {
         }
     }
     
-    fn generate_struct_impl_item(
+    pub(crate) fn generate_struct_impl_item(
         &self,
         generics: Generics,
         ident: Ident,
+        visibility: Visibility,
         fields: &Vec<Field>
     ) -> ItemImpl {
         let mut methods = vec![
-            Field::generate_new_method(fields)
+            Field::generate_new_method(&self.naming(), visibility.clone(), fields)
         ];
         let mut set_methods = fields.iter()
-            .map(|field| field.generate_set_method())
+            .filter(|field| !field.is_skipped)
+            .flat_map(|field| field.generate_set_methods())
             .sorted_by(|a, b| {
                 let a = match a {
                     ImplItem::Fn(value) => value,
@@ -187,7 +652,10 @@ impl Generator {
             })
             .collect();
         methods.append(&mut set_methods);
-        methods.push(Self::generate_build_method(&ident, fields));
+        methods.push(self.generate_build_method(&ident, visibility.clone(), fields));
+        if Field::has_fallible_fields(fields) {
+            methods.push(self.generate_try_build_method(&ident, visibility, fields));
+        }
         ItemImpl {
             attrs: Vec::new(),
             defaultness: None,
@@ -197,103 +665,203 @@ impl Generator {
             trait_: None,
             self_ty: Box::new(Type::Path(TypePath {
                 qself: None,
-                path: Path::new(&Self::ident(&ident).to_string()).to_syn_path(),
+                path: Path::new(&self.ident(&ident).to_string()).to_syn_path(),
             })),
             brace_token: Default::default(),
             items: methods,
         }
     }
 
-    fn generate_inner_builders(
+    fn generate_builder(
         &self,
         attributes: Vec<Attribute>,
         visibility: Visibility,
         generics: Generics,
+        ident: Ident,
+        module: Option<String>,
         fields: &Vec<Field>
-    ) -> Vec<StructItem> {
-        fn generate_builder(
-            generator: &Generator,
-            attributes: Vec<Attribute>,
-            visibility: Visibility,
-            generics: Generics,
-            ident: Ident,
-            fields: &Vec<Field>
-        ) -> Vec<StructItem> {
-            let struct_item = generator.generate_struct_item(
-                attributes.clone(),
-                visibility.clone(),
-                generics.clone(),
-                ident.clone(),
-                fields
-            );
-            let struct_impl_item = generator.generate_struct_impl_item(
-                generics.clone(),
-                ident.clone(),
-                fields
-            );
-            let mut builders = vec![
-                StructItem::new(struct_item, vec![struct_impl_item])
-            ];
-            let mut inner_builders = fields.iter()
-                .filter_map(|field| {
-                    match &field.ty.inner {
-                        InnerFieldTypeSegment::Complex(value) => {
-                            Some(generate_builder(
-                                generator,
-                                attributes.clone(),
-                                visibility.clone(),
-                                generics.clone(),
-                                create_ident(value.ident.clone()),
-                                &value.inner
-                            ))
-                        }
-                        _ => None
-                    }
-                })
-                .flatten()
-                .collect::<Vec<_>>();
-            builders.append(&mut inner_builders);
-            builders
-        }
-        let builders = fields.iter()
+    ) -> GeneratedBuilder {
+        let struct_item = self.generate_struct_item(
+            attributes,
+            visibility.clone(),
+            generics.clone(),
+            ident.clone(),
+            fields
+        );
+        let struct_impl_item = self.generate_struct_impl_item(
+            generics,
+            ident,
+            visibility,
+            fields
+        );
+        let dependencies = fields.iter()
+            .filter(|field| !field.is_skipped)
             .filter_map(|field| {
-                match &field.ty.inner {
-                    InnerFieldTypeSegment::Complex(value) => {
-                        let builders = generate_builder(
-                            self,
-                            attributes.clone(),
-                            visibility.clone(),
-                            generics.clone(),
-                            create_ident(value.ident.clone()),
-                            &value.inner
-                        );
-                        Some(builders)
-                    }
-                    _ => return None
+                match &field.ty.target().inner {
+                    InnerFieldTypeSegment::Complex(value) => Some(value.module.clone()),
+                    _ => None
                 }
             })
-            .flatten()
-            .collect::<Vec<_>>();
-        builders
+            .unique()
+            .collect();
+        GeneratedBuilder {
+            module,
+            dependencies,
+            item: struct_item,
+            impl_item: struct_impl_item,
+        }
+    }
+
+    fn wrap_builders(builders: Vec<GeneratedBuilder>) -> Vec<StructItem> {
+        builders.into_iter()
+            .map(|builder| StructItem::new(builder.item, vec![builder.impl_item]))
+            .collect()
+    }
+
+    fn generate_inner_builders(
+        &self,
+        fields: &Vec<Field>,
+        registry: &mut HashMap<String, String>
+    ) -> Result<Vec<GeneratedBuilder>, GenerateError> {
+        let mut builders = Vec::new();
+        for field in fields.iter().filter(|field| !field.is_skipped) {
+            if let InnerFieldTypeSegment::Complex(value) = &field.ty.target().inner {
+                let ident = create_ident(value.ident.clone());
+                let path = Self::type_path(Some(&value.module), &value.ident);
+                if !Self::register(registry, value.ident.clone(), self.ident(&ident).to_string(), path)? {
+                    continue;
+                }
+                builders.push(self.generate_builder(
+                    self.attribute_policy.borrow().apply(&value.attributes),
+                    value.visibility.clone(),
+                    value.generics.clone(),
+                    ident,
+                    Some(value.module.clone()),
+                    &value.inner
+                ));
+                let mut inner_builders = self.generate_inner_builders(&value.inner, registry)?;
+                builders.append(&mut inner_builders);
+            }
+        }
+        Ok(builders)
     }
     
     fn generate_build_method(
-        return_type: &Ident, 
+        &self,
+        return_type: &Ident,
+        visibility: Visibility,
         fields: &Vec<Field>
     ) -> ImplItem {
         let return_type = Path::from(return_type.clone());
-        let mut statements = Vec::new();
         let fields_init = fields.iter()
             .map(|field| {
-                (field.ident.clone(), Expr::Stmt(field.generate_build_method_statement()))
+                (field.target_ident.clone(), Expr::Stmt(field.generate_build_method_statement()))
             })
             .collect::<HashMap<_, _>>();
-        statements.push(Statement::implicit_return(
+        let mut statement = Statement::implicit_return(
             Expr::Stmt(Statement::struct_literal(Path::new(return_type.to_string()), fields_init))
-        ));
-        let item = ImplItem::Fn(ImplItemFn {
-            attrs: vec![],
-            vis: Visibility::Public(Default::default()),
+        );
+        Field::gate_struct_literal(&mut statement, fields, |field| field.target_ident.clone());
+        let statements = vec![statement];
+        let mut lines = vec![format!("Builds a new `{}`.", return_type)];
+        let required_fields = Self::required_fields(fields);
+        let fallible_fields = Self::fallible_fields(fields);
+        if !required_fields.is_empty() || !fallible_fields.is_empty() {
+            lines.append(&mut vec![String::new(), "# Panics".to_string(), String::new()]);
+        }
+        if !required_fields.is_empty() {
+            lines.push(format!("Panics if a required field is not set: {}.", required_fields));
+        }
+        if !fallible_fields.is_empty() {
+            lines.push(format!("Panics if a field conversion fails: {}.", fallible_fields));
+        }
+        Self::generate_build_method_item(
+            create_ident(&self.naming.borrow().build_method),
+            Self::build_method_attributes(lines, fields),
+            visibility,
+            Path::from(return_type.to_string()),
+            statements
+        )
+    }
+
+    fn generate_try_build_method(
+        &self,
+        return_type: &Ident,
+        visibility: Visibility,
+        fields: &Vec<Field>
+    ) -> ImplItem {
+        let return_type = Path::from(return_type.clone());
+        let fields_init = fields.iter()
+            .map(|field| {
+                (field.target_ident.clone(), Expr::Stmt(field.generate_try_build_method_statement()))
+            })
+            .collect::<HashMap<_, _>>();
+        let mut statement = Statement::implicit_return(
+            Expr::Stmt(Statement::call(
+                Path::new("Ok"),
+                vec![
+                    Expr::Stmt(Statement::struct_literal(Path::new(return_type.to_string()), fields_init))
+                ]
+            ))
+        );
+        Field::gate_struct_literal(&mut statement, fields, |field| field.target_ident.clone());
+        let statements = vec![statement];
+        let mut lines = vec![format!("Builds a new `{}`.", return_type)];
+        let required_fields = Self::required_fields(fields);
+        let fallible_fields = Self::fallible_fields(fields);
+        if !required_fields.is_empty() || !fallible_fields.is_empty() {
+            lines.append(&mut vec![String::new(), "# Errors".to_string(), String::new()]);
+        }
+        if !required_fields.is_empty() {
+            lines.push(format!("Returns an error if a required field is not set: {}.", required_fields));
+        }
+        if !fallible_fields.is_empty() {
+            lines.push(format!("Returns an error if a field conversion fails: {}.", fallible_fields));
+        }
+        Self::generate_build_method_item(
+            create_ident(&self.naming.borrow().try_build_method),
+            Self::build_method_attributes(lines, fields),
+            visibility,
+            create_generic_type(
+                "Result",
+                vec![Path::from(return_type.to_string()), Path::new("String")]
+            ),
+            statements
+        )
+    }
+
+    fn build_method_attributes(lines: Vec<String>, fields: &Vec<Field>) -> Vec<Attribute> {
+        let mut attributes = doc_attributes(lines);
+        if fields.iter().any(|field| field.is_deprecated()) {
+            attributes.push(parse_quote!(#[allow(deprecated)]));
+        }
+        attributes
+    }
+
+    fn required_fields(fields: &Vec<Field>) -> String {
+        fields.iter()
+            .filter(|field| !field.is_skipped && field.is_required)
+            .map(|field| format!("`{}`", field.ident))
+            .join(", ")
+    }
+
+    fn fallible_fields(fields: &Vec<Field>) -> String {
+        fields.iter()
+            .filter(|field| !field.is_skipped && field.has_fallible_conversion())
+            .map(|field| format!("`{}`", field.ident))
+            .join(", ")
+    }
+
+    fn generate_build_method_item(
+        ident: Ident,
+        attributes: Vec<Attribute>,
+        visibility: Visibility,
+        return_type: Path,
+        statements: Vec<Stmt>
+    ) -> ImplItem {
+        ImplItem::Fn(ImplItemFn {
+            attrs: attributes,
+            vis: visibility,
             defaultness: None,
             sig: Signature {
                 constness: None,
@@ -301,7 +869,7 @@ impl Generator {
                 unsafety: None,
                 abi: None,
                 fn_token: Default::default(),
-                ident: create_ident("build"),
+                ident,
                 generics: Default::default(),
                 paren_token: Default::default(),
                 inputs: Punctuated::single(FnArg::Receiver(Receiver {
@@ -323,14 +891,13 @@ impl Generator {
                 variadic: None,
                 output: ReturnType::Type(Default::default(), Box::new(Type::Path(TypePath {
                     qself: None,
-                    path: Path::from(return_type.to_string()).to_syn_path(),
+                    path: return_type.to_syn_path(),
                 }))),
             },
             block: Block {
                 brace_token: Default::default(),
                 stmts: statements,
             },
-        });
-        item
+        })
     }
 }
\ No newline at end of file
@@ -0,0 +1,39 @@
+use crate::constants::{KEYWORDS, NON_RAW_KEYWORDS};
+use crate::rule_error::RuleError;
+use quote::format_ident;
+use syn::{parse_str, Ident};
+
+pub(crate) fn unraw(ident: &str) -> String {
+    ident.strip_prefix("r#").unwrap_or(ident).to_string()
+}
+
+pub(crate) fn escape(ident: &str) -> String {
+    let ident = unraw(ident);
+    if NON_RAW_KEYWORDS.contains(&ident.as_str()) {
+        panic!("Keyword '{}' cannot be used as an identifier", ident);
+    }
+    match KEYWORDS.contains(&ident.as_str()) {
+        true => format!("r#{}", ident),
+        false => ident
+    }
+}
+
+pub(crate) fn create_escaped_ident(ident: &str) -> Ident {
+    format_ident!("{}", escape(ident))
+}
+
+pub(crate) fn validate_ident(ident: &str) -> Result<(), RuleError> {
+    if NON_RAW_KEYWORDS.contains(&unraw(ident).as_str()) {
+        return Err(RuleError::InvalidIdent {
+            ident: ident.to_string(),
+            message: "keyword cannot be used as an identifier".to_string(),
+        });
+    }
+    if let Err(error) = parse_str::<Ident>(escape(ident).as_str()) {
+        return Err(RuleError::InvalidIdent {
+            ident: ident.to_string(),
+            message: error.to_string(),
+        });
+    }
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,67 @@
+use crate::constants::REGEX_SYNTAX;
+use crate::rule_error::RuleError;
+use glob::Pattern;
+use regex::Regex;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone)]
+pub(crate) enum IdentPattern {
+    Exact(String),
+    Glob(Pattern),
+    Regex(Regex)
+}
+
+impl IdentPattern {
+    pub(crate) fn exact(ident: impl Into<String>) -> Self {
+        IdentPattern::Exact(ident.into())
+    }
+
+    pub(crate) fn try_matching(pattern: impl Into<String>) -> Result<Self, RuleError> {
+        let pattern = pattern.into();
+        if pattern.chars().any(|character| REGEX_SYNTAX.contains(&character)) {
+            return Self::try_regex(pattern);
+        }
+        match Pattern::new(pattern.as_str()) {
+            Ok(value) => Ok(IdentPattern::Glob(value)),
+            Err(error) => Err(RuleError::InvalidPattern {
+                pattern,
+                message: error.to_string(),
+            })
+        }
+    }
+
+    pub(crate) fn try_regex(pattern: impl Into<String>) -> Result<Self, RuleError> {
+        let pattern = pattern.into();
+        match Regex::new(pattern.as_str()) {
+            Ok(value) => Ok(IdentPattern::Regex(value)),
+            Err(error) => Err(RuleError::InvalidPattern {
+                pattern,
+                message: error.to_string(),
+            })
+        }
+    }
+
+    pub(crate) fn matches(&self, ident: &str) -> bool {
+        match self {
+            IdentPattern::Exact(value) => value == ident,
+            IdentPattern::Glob(value) => value.matches(ident),
+            IdentPattern::Regex(value) => value.is_match(ident)
+        }
+    }
+}
+
+impl Display for IdentPattern {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IdentPattern::Exact(value) => {
+                write!(f, "{}", value)
+            }
+            IdentPattern::Glob(value) => {
+                write!(f, "glob({})", value)
+            }
+            IdentPattern::Regex(value) => {
+                write!(f, "regex({})", value)
+            }
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,109 @@
+use crate::constants::COLLECTION_TYPES;
+use quote::format_ident;
+use std::collections::BTreeSet;
+use syn::visit::{self, Visit};
+use syn::{parse_quote, parse_str, Item, Path, UseTree};
+
+pub(crate) fn collection_imports(items: &[Item]) -> Option<Item> {
+    struct CollectionVisitor {
+        collections: BTreeSet<String>,
+    }
+
+    impl<'ast> Visit<'ast> for CollectionVisitor {
+        fn visit_path(&mut self, node: &'ast Path) {
+            if let Some(segment) = node.segments.first() {
+                let ident = segment.ident.to_string();
+                if node.leading_colon.is_none() && COLLECTION_TYPES.contains(&ident.as_str()) {
+                    self.collections.insert(ident);
+                }
+            }
+            visit::visit_path(self, node);
+        }
+    }
+
+    let mut visitor = CollectionVisitor {
+        collections: BTreeSet::new(),
+    };
+    for item in items.iter() {
+        visitor.visit_item(item);
+    }
+    if visitor.collections.is_empty() {
+        return None;
+    }
+    let collections = visitor.collections.iter()
+        .map(|collection| format_ident!("{}", collection));
+    Some(parse_quote!(use std::collections::{#(#collections),*};))
+}
+
+pub(crate) fn module_segments(module: &str) -> Vec<String> {
+    module.split("::")
+        .map(|segment| segment.trim().to_string())
+        .collect()
+}
+
+pub(crate) fn glob_import(from: &[String], to: &[String]) -> Item {
+    let common = from.iter()
+        .zip(to.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let mut segments = match common == from.len() {
+        true => vec!["self".to_string()],
+        false => vec!["super".to_string(); from.len() - common]
+    };
+    segments.extend(to[common..].iter().cloned());
+    let path = parse_str::<Path>(segments.join("::").as_str()).unwrap();
+    parse_quote!(use #path::*;)
+}
+
+pub(crate) fn imported_paths(items: &[Item], module: &[String], ident: &str) -> Vec<(Vec<String>, String)> {
+    fn collect(tree: &UseTree, prefix: Vec<String>, imports: &mut Vec<(Vec<String>, String, String)>) {
+        match tree {
+            UseTree::Path(value) => {
+                let mut prefix = prefix;
+                prefix.push(value.ident.to_string());
+                collect(&value.tree, prefix, imports);
+            }
+            UseTree::Name(value) => {
+                imports.push((prefix, value.ident.to_string(), value.ident.to_string()));
+            }
+            UseTree::Rename(value) => {
+                imports.push((prefix, value.ident.to_string(), value.rename.to_string()));
+            }
+            UseTree::Glob(_) => {
+                imports.push((prefix, String::new(), "*".to_string()));
+            }
+            UseTree::Group(value) => {
+                for tree in value.items.iter() {
+                    collect(tree, prefix.clone(), imports);
+                }
+            }
+        }
+    }
+    fn resolve(path: &[String], module: &[String]) -> Vec<String> {
+        match path.first().map(|segment| segment.as_str()) {
+            Some("crate") => path[1..].to_vec(),
+            Some("self") => [module, &path[1..]].concat(),
+            Some("super") => {
+                let supers = path.iter()
+                    .take_while(|segment| *segment == "super")
+                    .count();
+                let parent = &module[..module.len().saturating_sub(supers)];
+                [parent, &path[supers..]].concat()
+            }
+            _ => [module, path].concat()
+        }
+    }
+    let mut imports = Vec::new();
+    for item in items.iter() {
+        if let Item::Use(value) = item {
+            collect(&value.tree, Vec::new(), &mut imports);
+        }
+    }
+    let named = imports.iter()
+        .filter(|(_, _, name)| name == ident)
+        .map(|(path, item, _)| (resolve(path, module), item.clone()));
+    let globs = imports.iter()
+        .filter(|(_, _, name)| name == "*")
+        .map(|(path, _, _)| (resolve(path, module), ident.to_string()));
+    named.chain(globs).collect()
+}
\ No newline at end of file
@@ -1,8 +1,22 @@
 mod constants;
 
+mod attribute_policy;
+#[cfg(test)]
+#[path = "./attribute_policy_test.rs"]
+mod attribute_policy_test;
+
+mod doc;
+
+mod ident;
+
+mod imports;
+
 mod generator;
 pub use generator::Generator as BuilderGenerator;
 
+mod naming;
+pub use naming::Naming as BuilderNaming;
+
 mod field;
 #[cfg(test)]
 #[path = "./field_test.rs"]
@@ -13,7 +27,29 @@ mod field_rule;
 #[path = "./field_rule_test.rs"]
 mod field_rule_test;
 
+mod field_rule_selector;
+pub use field_rule_selector::FieldRuleSelector;
+
+mod generate_error;
+pub use generate_error::{Collision, CollisionKind, GenerateError};
+
+mod generated_module;
+pub use generated_module::{GeneratedModule, SkippedItem};
+
+mod rule_error;
+pub use rule_error::RuleError;
+
+mod rule_file;
+pub use rule_file::RuleFormat;
+
+mod rule_report;
+pub use rule_report::{RuleMatch, RuleReport, RuleReportEntry};
+
 mod field_type_segment;
 
+mod field_type_pattern;
+
+mod ident_pattern;
+
 #[cfg(test)]
 mod test_utils;
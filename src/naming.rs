@@ -0,0 +1,113 @@
+use ast_shaper::utils::create_ident;
+use syn::Ident;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Naming {
+    pub builder_prefix: String,
+    pub builder_suffix: String,
+    pub setter_prefix: String,
+    pub accessor_prefix: String,
+    pub replace_prefix: String,
+    pub replace_suffix: String,
+    pub unset_prefix: String,
+    pub unset_suffix: String,
+    pub new_method: String,
+    pub build_method: String,
+    pub try_build_method: String,
+}
+
+impl Naming {
+    pub fn builder_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.builder_prefix = prefix.into();
+        self
+    }
+
+    pub fn builder_suffix(mut self, suffix: impl Into<String>) -> Self {
+        self.builder_suffix = suffix.into();
+        self
+    }
+
+    pub fn setter_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.setter_prefix = prefix.into();
+        self
+    }
+
+    pub fn accessor_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.accessor_prefix = prefix.into();
+        self
+    }
+
+    pub fn replace_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.replace_prefix = prefix.into();
+        self
+    }
+
+    pub fn replace_suffix(mut self, suffix: impl Into<String>) -> Self {
+        self.replace_suffix = suffix.into();
+        self
+    }
+
+    pub fn unset_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.unset_prefix = prefix.into();
+        self
+    }
+
+    pub fn unset_suffix(mut self, suffix: impl Into<String>) -> Self {
+        self.unset_suffix = suffix.into();
+        self
+    }
+
+    pub fn new_method(mut self, ident: impl Into<String>) -> Self {
+        self.new_method = ident.into();
+        self
+    }
+
+    pub fn build_method(mut self, ident: impl Into<String>) -> Self {
+        self.build_method = ident.into();
+        self
+    }
+
+    pub fn try_build_method(mut self, ident: impl Into<String>) -> Self {
+        self.try_build_method = ident.into();
+        self
+    }
+
+    pub(crate) fn builder_ident(&self, ident: &Ident) -> Ident {
+        let ident = format!("{}{}{}", self.builder_prefix, ident, self.builder_suffix);
+        create_ident(&ident)
+    }
+
+    pub(crate) fn setter_ident(&self, field_ident: &str) -> String {
+        format!("{}{}", self.setter_prefix, field_ident)
+    }
+
+    pub(crate) fn accessor_ident(&self, field_ident: &str) -> String {
+        format!("{}{}", self.accessor_prefix, field_ident)
+    }
+
+    pub(crate) fn replace_ident(&self, field_ident: &str) -> String {
+        format!("{}{}{}", self.replace_prefix, field_ident, self.replace_suffix)
+    }
+
+    pub(crate) fn unset_ident(&self, field_ident: &str) -> String {
+        format!("{}{}{}", self.unset_prefix, field_ident, self.unset_suffix)
+    }
+}
+
+impl Default for Naming {
+    fn default() -> Self {
+        Self {
+            builder_prefix: String::new(),
+            builder_suffix: "Builder".to_string(),
+            setter_prefix: "with_".to_string(),
+            accessor_prefix: "with_".to_string(),
+            replace_prefix: "set_".to_string(),
+            replace_suffix: String::new(),
+            unset_prefix: "unset_".to_string(),
+            unset_suffix: String::new(),
+            new_method: "new".to_string(),
+            build_method: "build".to_string(),
+            try_build_method: "try_build".to_string(),
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,89 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RuleError {
+    SelectorTargetsAllFields {
+        action: String,
+        selector: String,
+    },
+    InvalidConverter {
+        converter: String,
+        message: String,
+    },
+    InvalidDefault {
+        default: String,
+        message: String,
+    },
+    InvalidType {
+        ty: String,
+        message: String,
+    },
+    InvalidVisibility {
+        visibility: String,
+        message: String,
+    },
+    InvalidPattern {
+        pattern: String,
+        message: String,
+    },
+    InvalidIdent {
+        ident: String,
+        message: String,
+    },
+    MissingAction {
+        index: usize,
+    },
+    UnsupportedFormat {
+        path: String,
+    },
+    UnreadableRuleFile {
+        path: String,
+        message: String,
+    },
+    InvalidRuleFile {
+        message: String,
+    },
+}
+
+impl Display for RuleError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RuleError::SelectorTargetsAllFields { action, selector } => {
+                write!(f, "Cannot {} field when field selector target all field: {}", action, selector)
+            }
+            RuleError::InvalidConverter { converter, message } => {
+                write!(f, "Invalid converter path '{}': {}", converter, message)
+            }
+            RuleError::InvalidDefault { default, message } => {
+                write!(f, "Invalid default expression '{}': {}", default, message)
+            }
+            RuleError::InvalidType { ty, message } => {
+                write!(f, "Invalid type '{}': {}", ty, message)
+            }
+            RuleError::InvalidVisibility { visibility, message } => {
+                write!(f, "Invalid visibility '{}': {}", visibility, message)
+            }
+            RuleError::InvalidPattern { pattern, message } => {
+                write!(f, "Invalid pattern '{}': {}", pattern, message)
+            }
+            RuleError::InvalidIdent { ident, message } => {
+                write!(f, "Invalid identifier '{}': {}", ident, message)
+            }
+            RuleError::MissingAction { index } => {
+                write!(f, "Rule #{} does not declare any action", index)
+            }
+            RuleError::UnsupportedFormat { path } => {
+                write!(f, "Unsupported rule file format '{}': expected .toml or .json", path)
+            }
+            RuleError::UnreadableRuleFile { path, message } => {
+                write!(f, "Cannot read rule file '{}': {}", path, message)
+            }
+            RuleError::InvalidRuleFile { message } => {
+                write!(f, "Invalid rule file: {}", message)
+            }
+        }
+    }
+}
+
+impl Error for RuleError {}
\ No newline at end of file
@@ -0,0 +1,150 @@
+use crate::field_rule::{FieldRule, FieldRuleItemSelectorBuilder};
+use crate::field_rule_selector::FieldRuleSelector;
+use crate::rule_error::RuleError;
+use ast_shaper::utils::path::Path;
+use serde::Deserialize;
+use std::cell::RefCell;
+use std::rc::Rc;
+use syn::parse_str;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RuleFormat {
+    Toml,
+    Json
+}
+
+impl RuleFormat {
+    pub(crate) fn from_path(path: &std::path::Path) -> Result<Self, RuleError> {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("toml") => Ok(RuleFormat::Toml),
+            Some("json") => Ok(RuleFormat::Json),
+            _ => Err(RuleError::UnsupportedFormat {
+                path: path.display().to_string(),
+            })
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct RuleFile {
+    #[serde(default)]
+    rules: Vec<RuleDefinition>
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct RuleDefinition {
+    item: Option<String>,
+    items_matching: Option<String>,
+    items_matching_regex: Option<String>,
+    item_attribute: Option<String>,
+    field: Option<String>,
+    fields_matching: Option<String>,
+    fields_matching_regex: Option<String>,
+    field_type: Option<String>,
+    field_attribute: Option<String>,
+    #[serde(default)]
+    except: Vec<String>,
+    rename: Option<String>,
+    map: Option<String>,
+    default: Option<String>,
+    #[serde(default)]
+    skip: bool,
+    #[serde(default)]
+    discard_attribute: Vec<String>,
+}
+
+impl RuleFile {
+    pub(crate) fn parse(content: &str, format: RuleFormat) -> Result<Self, RuleError> {
+        match format {
+            RuleFormat::Toml => toml::from_str(content)
+                .map_err(|error| RuleError::InvalidRuleFile {
+                    message: error.to_string(),
+                }),
+            RuleFormat::Json => serde_json::from_str(content)
+                .map_err(|error| RuleError::InvalidRuleFile {
+                    message: error.to_string(),
+                })
+        }
+    }
+
+    pub(crate) fn into_rules(self) -> Result<Vec<FieldRule>, RuleError> {
+        let rules = Rc::new(RefCell::new(Vec::new()));
+        for (index, definition) in self.rules.into_iter().enumerate() {
+            definition.register(index, rules.clone())?;
+        }
+        let rules = rules.take();
+        Ok(rules)
+    }
+}
+
+impl RuleDefinition {
+    pub(self) fn register(self, index: usize, rules: Rc<RefCell<Vec<FieldRule>>>) -> Result<(), RuleError> {
+        let has_action = self.rename.is_some()
+            || self.map.is_some()
+            || self.default.is_some()
+            || self.skip
+            || !self.discard_attribute.is_empty();
+        if !has_action {
+            return Err(RuleError::MissingAction {
+                index,
+            });
+        }
+        let item_selector = Self::combine(vec![
+            self.item.map(FieldRuleSelector::item),
+            self.items_matching.map(FieldRuleSelector::try_items_matching).transpose()?,
+            self.items_matching_regex.map(FieldRuleSelector::try_items_matching_regex).transpose()?,
+            self.item_attribute.map(FieldRuleSelector::item_attribute),
+        ]);
+        let field_selector = Self::combine(vec![
+            self.field.map(FieldRuleSelector::field_ident),
+            self.fields_matching.map(FieldRuleSelector::try_field_ident_matching).transpose()?,
+            self.fields_matching_regex.map(FieldRuleSelector::try_field_ident_matching_regex).transpose()?,
+            self.field_type.map(FieldRuleSelector::try_field_type).transpose()?,
+            self.field_attribute.map(FieldRuleSelector::field_attribute),
+        ]);
+        let definition_rules = Rc::new(RefCell::new(Vec::new()));
+        let mut field_builder = FieldRuleItemSelectorBuilder::new(definition_rules.clone())
+            .for_items(item_selector);
+        let mut rule = field_builder.with_fields(field_selector);
+        for ident in self.except {
+            rule.except_field(ident);
+        }
+        if let Some(ident) = self.rename {
+            rule.try_then_rename(ident)?;
+        }
+        if let Some(ty) = self.map {
+            let ty = match parse_str::<syn::Path>(ty.as_str()) {
+                Ok(value) => Path::from(value),
+                Err(error) => return Err(RuleError::InvalidType {
+                    ty,
+                    message: error.to_string(),
+                })
+            };
+            rule.try_then_map(ty)?;
+        }
+        match (self.skip, self.default) {
+            (true, default) => {
+                rule.try_then_skip(default.unwrap_or("Default::default()".to_string()))?;
+            }
+            (false, Some(default)) => {
+                rule.try_then_default(default)?;
+            }
+            (false, None) => {}
+        }
+        for attribute in self.discard_attribute {
+            rule.try_then_discard_attribute(attribute)?;
+        }
+        if let Some(rule) = FieldRule::merge(definition_rules.take()) {
+            rules.borrow_mut().push(rule);
+        }
+        Ok(())
+    }
+
+    pub(self) fn combine(selectors: Vec<Option<FieldRuleSelector>>) -> FieldRuleSelector {
+        selectors.into_iter()
+            .flatten()
+            .fold(FieldRuleSelector::all(), |selector, other| selector.and(other))
+    }
+}
\ No newline at end of file
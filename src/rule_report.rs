@@ -0,0 +1,66 @@
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleMatch {
+    pub item: String,
+    pub field: String,
+}
+
+impl Display for RuleMatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.item, self.field)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RuleReportEntry {
+    pub rule: String,
+    pub selector: String,
+    pub matches: Vec<RuleMatch>,
+}
+
+impl RuleReportEntry {
+    pub fn is_unmatched(&self) -> bool {
+        self.matches.is_empty()
+    }
+}
+
+impl Display for RuleReportEntry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} when {}", self.rule, self.selector)?;
+        if self.is_unmatched() {
+            return write!(f, "\n  (unmatched)");
+        }
+        for rule_match in self.matches.iter() {
+            write!(f, "\n  {}", rule_match)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RuleReport {
+    pub entries: Vec<RuleReportEntry>,
+}
+
+impl RuleReport {
+    pub fn unmatched(&self) -> Vec<&RuleReportEntry> {
+        self.entries.iter()
+            .filter(|entry| entry.is_unmatched())
+            .collect()
+    }
+
+    pub fn has_unmatched(&self) -> bool {
+        self.entries.iter().any(|entry| entry.is_unmatched())
+    }
+}
+
+impl Display for RuleReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let entries = self.entries.iter()
+            .map(|entry| entry.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+        write!(f, "{}", entries)
+    }
+}
\ No newline at end of file
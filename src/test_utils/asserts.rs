@@ -5,10 +5,11 @@ use ast_shaper::items::module_item::ModuleItem;
 use ast_shaper::items::struct_item::StructItem;
 use ast_shaper::utils::parsing::TokenStreamExt;
 use ast_shaper::utils::path::Path;
+use itertools::Itertools;
 use pretty_assertions::assert_eq;
 use quote::ToTokens;
 use quote::__private::TokenStream;
-use syn::{parse2, Fields, ImplItemFn, Type};
+use syn::{parse2, Expr, FieldValue, Fields, ImplItemFn, Stmt, Type};
 
 fn internal_assert_builder(
     item_ident: String,
@@ -104,4 +105,32 @@ pub fn assert_method(functions: &Vec<FnItem>, expected_method: TokenStream) {
             method_statements[index].to_token_stream().to_string()
         );
     }
+}
+
+pub fn assert_matched_fields(generator: &Generator, item: &syn::Item, expected_fields: Vec<&str>) {
+    let matched_fields = generator.generate_fields(item).iter()
+        .filter(|field| field.ident == "matched_field")
+        .map(|field| field.target_ident.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(expected_fields, matched_fields);
+}
+
+pub fn assert_struct_literal_fields(functions: &Vec<FnItem>, method_ident: &str, expected_fields: Vec<TokenStream>) {
+    let method = functions.iter()
+        .find(|function| function.ident() == method_ident)
+        .unwrap();
+    let fields = match method.item.block().stmts.first() {
+        Some(Stmt::Expr(Expr::Struct(value), None)) => {
+            value.fields.iter()
+                .map(|field| field.to_token_stream().to_string())
+                .sorted()
+                .collect::<Vec<_>>()
+        }
+        _ => panic!("Expected struct literal")
+    };
+    let expected_fields = expected_fields.iter()
+        .map(|field| parse2::<FieldValue>(field.clone()).unwrap().to_token_stream().to_string())
+        .sorted()
+        .collect::<Vec<_>>();
+    assert_eq!(expected_fields, fields);
 }
\ No newline at end of file
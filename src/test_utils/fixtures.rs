@@ -1,9 +1,12 @@
 use crate::generator::Generator;
+use ast_shaper::items::item::Item;
 use ast_shaper::items::module_item::ModuleItem;
+use ast_shaper::items::struct_item::StructItem;
 use ast_shaper::test_utils::fixtures::{struct_with_boxed_field, struct_with_complex_field, struct_with_field_attributes, struct_with_optional_and_boxed_field, struct_with_optional_and_optional_field, struct_with_optional_field, struct_with_ref_counter_and_refcell_field, struct_with_required_field, struct_with_vec_of_primitive_field};
 use rstest::fixture;
 use std::cell::RefCell;
 use std::rc::Rc;
+use syn::parse_quote;
 
 #[fixture]
 pub fn generator(
@@ -29,4 +32,148 @@ pub fn generator(
         struct_with_field_attributes
     ]));
     Generator::new(modules)
+}
+
+#[fixture]
+pub fn struct_with_mixed_fields() -> syn::Item {
+    parse_quote! {
+        pub struct StructWithMixedFields {
+            field_u32: u32,
+            field_vec: Vec<u32>,
+            field_vec_of_string: Vec<String>,
+            field_option: Option<u32>,
+            field_map: HashMap<String, u32>,
+            owner_id: u64,
+        }
+    }
+}
+
+#[fixture]
+pub fn struct_with_item_attributes() -> syn::Item {
+    parse_quote! {
+        #[derive(Debug, Serialize)]
+        pub struct StructWithItemAttributes {
+            #[serde(skip)]
+            field_skipped: u32,
+            #[deprecated]
+            field_deprecated: u32,
+            field: u32,
+        }
+    }
+}
+
+#[fixture]
+pub fn struct_with_builder_attributes() -> syn::Item {
+    parse_quote! {
+        pub struct StructWithBuilderAttributes {
+            #[builder(rename = "port", default = "8080")]
+            #[serde(default)]
+            field_port: u16,
+            #[builder(skip)]
+            field_skipped: u32,
+            #[builder(into)]
+            field_name: String,
+        }
+    }
+}
+
+
+#[fixture]
+pub fn struct_with_private_visibility() -> syn::Item {
+    parse_quote! {
+        struct StructWithPrivateVisibility {
+            field: u32,
+            field_internal: u32,
+        }
+    }
+}
+
+#[fixture]
+pub fn struct_with_documented_fields() -> syn::Item {
+    parse_quote! {
+        pub struct StructWithDocumentedFields {
+            /// The port to listen on.
+            field_port: u16,
+            field_name: Option<String>,
+        }
+    }
+}
+
+#[fixture]
+pub fn struct_with_gated_fields() -> syn::Item {
+    parse_quote! {
+        pub struct StructWithGatedFields {
+            #[cfg(feature = "tls")]
+            field_tls: bool,
+            #[deprecated]
+            field_legacy: u32,
+            #[cfg_attr(feature = "serde", serde(rename = "x"))]
+            field_renamed: u32,
+            #[cfg_attr(feature = "legacy", deprecated, serde(skip))]
+            field_old: u32,
+        }
+    }
+}
+
+#[fixture]
+pub fn struct_with_shared_complex_fields() -> syn::Item {
+    parse_quote! {
+        pub struct StructWithSharedComplexFields {
+            field: ComplexType,
+            other_field: ComplexType,
+        }
+    }
+}
+
+#[fixture]
+pub fn modules_with_same_item_names(
+    struct_with_required_field: ModuleItem,
+    struct_with_optional_field: ModuleItem,
+    struct_with_optional_and_optional_field: ModuleItem
+) -> Vec<ModuleItem> {
+    let mut first = struct_with_required_field;
+    first.items = vec![Item::Struct(StructItem::new(parse_quote!(pub struct Config { port: u16 }), vec![]))];
+    let mut second = struct_with_optional_field;
+    second.items = vec![
+        Item::Struct(StructItem::new(parse_quote!(pub struct Settings { config: Config }), vec![])),
+        Item::Struct(StructItem::new(parse_quote!(pub struct Config { name: String }), vec![])),
+    ];
+    let mut third = struct_with_optional_and_optional_field;
+    third.items = vec![Item::Struct(StructItem::new(parse_quote!(pub struct Service { config: Config }), vec![]))];
+    vec![first, second, third]
+}
+
+#[fixture]
+pub fn modules_with_nested_item(
+    struct_with_required_field: ModuleItem,
+    struct_with_optional_field: ModuleItem
+) -> Vec<ModuleItem> {
+    let mut first = struct_with_required_field;
+    first.items = vec![Item::Struct(StructItem::new(parse_quote!(pub struct Outer { inner: Inner }), vec![]))];
+    let mut second = struct_with_optional_field;
+    second.items = vec![Item::Struct(StructItem::new(parse_quote!(pub struct Inner { field: u32 }), vec![]))];
+    vec![first, second]
+}
+
+#[fixture]
+pub fn module_with_nested_item_attributes(
+    struct_with_required_field: ModuleItem
+) -> ModuleItem {
+    let mut module = struct_with_required_field;
+    module.items = vec![
+        Item::Struct(StructItem::new(parse_quote! {
+            #[derive(Debug, Serialize)]
+            #[serde(rename_all = "camelCase")]
+            pub struct Outer {
+                inner: Inner,
+            }
+        }, vec![])),
+        Item::Struct(StructItem::new(parse_quote! {
+            #[derive(Clone)]
+            pub struct Inner {
+                field: u32,
+            }
+        }, vec![]))
+    ];
+    module
 }
\ No newline at end of file